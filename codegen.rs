@@ -5,6 +5,9 @@ pub fn generate() {
         "protos/streams.proto",
         "protos/shared.proto",
         "protos/gossip.proto",
+        "protos/projections.proto",
+        "protos/users.proto",
+        "protos/operations.proto",
     ];
 
     tonic_build::configure()
@@ -29,6 +32,9 @@ pub fn generate() {
             let new_file = file.path().parent().unwrap().join(new_file_name);
 
             std::fs::rename(file.path(), new_file).unwrap();
+        } else if filename_string == "google.protobuf.rs" {
+            // Well-known types are provided by `prost-types`.
+            std::fs::remove_file(file.path()).unwrap();
         }
     }
 }
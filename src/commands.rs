@@ -1,23 +1,33 @@
 //! Commands this client supports.
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
 use futures::{stream, TryStreamExt};
 use futures::{Stream, StreamExt};
 
-use crate::event_store::client::{persistent, shared, streams};
+use crate::event_store::client::{operations, persistent, projections, shared, streams, users};
 use crate::types::{
-    EventData, ExpectedRevision, ExpectedVersion, PersistentSubscriptionSettings, Position,
-    ReadDirection, RecordedEvent, ResolvedEvent, Revision, WriteResult, WrongExpectedVersion,
+    ChunkedWriteResult, Endpoint, EventData, ExpectedRevision, ExpectedVersion, PersistentSubEvent,
+    PersistentSubscriptionSettings, Position, ProjectionDetails, ProjectionListMode,
+    ProjectionMode, ProjectionPosition, ProjectionStatus, ReadAllItem, ReadDirection,
+    RecordedEvent, ResolvedEvent, RetryPolicy, Revision, ScavengeResult, ScavengeStatus,
+    StreamMetadata, StreamMetadataResult, SubEvent, SubscriptionDropReason, UserDetails,
+    VersionedMetadata, WriteResult, WrongExpectedVersion,
 };
 
+use operations::operations_client::OperationsClient;
 use persistent::persistent_subscriptions_client::PersistentSubscriptionsClient;
+use projections::projections_client::ProjectionsClient;
 use shared::{Empty, StreamIdentifier, Uuid};
 use std::marker::Unpin;
 use streams::append_req::options::ExpectedStreamRevision;
 use streams::streams_client::StreamsClient;
+use users::users_client::UsersClient;
 
 use crate::grpc_connection::GrpcConnection;
 use crate::{Credentials, CurrentRevision, LinkTos, NakAction, ReadResult, SystemConsumerStrategy};
+use serde::de::DeserializeOwned;
 use tonic::Request;
 
 fn convert_expected_version(version: ExpectedVersion) -> ExpectedStreamRevision {
@@ -112,6 +122,87 @@ fn convert_event_data(event: EventData) -> streams::AppendReq {
     }
 }
 
+// System metadata the server attaches to every recorded event.
+struct SystemMetadata {
+    event_type: String,
+    is_json: bool,
+    content_type: String,
+    created: Option<SystemTime>,
+}
+
+impl SystemMetadata {
+    fn parse(metadata: &mut HashMap<String, String>) -> Self {
+        let event_type = metadata
+            .remove("type")
+            .unwrap_or_else(|| "<no-event-type-provided>".to_owned());
+
+        let content_type = metadata.remove("content-type");
+
+        // Older servers only send `is-json`. Unknown values fall back to
+        // the content type.
+        let is_json = match metadata.remove("is-json").map(|v| v.to_lowercase()) {
+            Some(ref v) if v == "true" => true,
+            Some(ref v) if v == "false" => false,
+            _ => content_type.as_deref() == Some("application/json"),
+        };
+
+        let content_type = content_type.unwrap_or_else(|| {
+            if is_json {
+                "application/json".to_owned()
+            } else {
+                "application/octet-stream".to_owned()
+            }
+        });
+
+        let created = metadata
+            .remove("created")
+            .and_then(|ticks| ticks.parse().ok())
+            .and_then(ticks_to_system_time);
+
+        SystemMetadata {
+            event_type,
+            is_json,
+            content_type,
+            created,
+        }
+    }
+}
+
+// The server counts 100-nanosecond ticks since the Unix epoch.
+fn ticks_to_system_time(ticks: i64) -> Option<SystemTime> {
+    use std::convert::TryFrom;
+
+    let ticks = u64::try_from(ticks).ok()?;
+
+    std::time::UNIX_EPOCH.checked_add(std::time::Duration::from_nanos(ticks.checked_mul(100)?))
+}
+
+#[test]
+fn test_parse_system_metadata() {
+    let mut metadata = HashMap::new();
+
+    metadata.insert("type".to_string(), "foo".to_string());
+    metadata.insert("content-type".to_string(), "application/json".to_string());
+    metadata.insert("created".to_string(), "16000000000000000".to_string());
+    metadata.insert("is-json".to_string(), "maybe".to_string());
+
+    let parsed = SystemMetadata::parse(&mut metadata);
+
+    assert_eq!(parsed.event_type, "foo");
+    assert!(parsed.is_json);
+    assert_eq!(parsed.content_type, "application/json");
+    assert_eq!(
+        parsed.created,
+        Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000))
+    );
+
+    let parsed = SystemMetadata::parse(&mut HashMap::new());
+
+    assert!(!parsed.is_json);
+    assert_eq!(parsed.content_type, "application/octet-stream");
+    assert_eq!(parsed.created, None);
+}
+
 fn convert_proto_recorded_event(
     mut event: streams::read_resp::read_event::RecordedEvent,
 ) -> RecordedEvent {
@@ -125,21 +216,7 @@ fn convert_proto_recorded_event(
         prepare: event.prepare_position,
     };
 
-    let event_type = if let Some(tpe) = event.metadata.remove(&"type".to_owned()) {
-        tpe
-    } else {
-        "<no-event-type-provided>".to_owned()
-    };
-
-    let is_json = if let Some(is_json) = event.metadata.remove(&"is-json".to_owned()) {
-        match is_json.to_lowercase().as_str() {
-            "true" => true,
-            "false" => false,
-            unknown => panic!("Unknown [{}] 'is-json' metadata value", unknown),
-        }
-    } else {
-        false
-    };
+    let metadata = SystemMetadata::parse(&mut event.metadata);
 
     let stream_id = String::from_utf8(
         event
@@ -153,8 +230,10 @@ fn convert_proto_recorded_event(
         stream_id,
         revision: event.stream_revision,
         position,
-        event_type,
-        is_json,
+        event_type: metadata.event_type,
+        is_json: metadata.is_json,
+        content_type: metadata.content_type,
+        created: metadata.created,
         metadata: event.custom_metadata.into(),
         data: event.data.into(),
     }
@@ -173,21 +252,7 @@ fn convert_persistent_proto_recorded_event(
         prepare: event.prepare_position,
     };
 
-    let event_type = if let Some(tpe) = event.metadata.remove(&"type".to_owned()) {
-        tpe
-    } else {
-        "<no-event-type-provided>".to_owned()
-    };
-
-    let is_json = if let Some(is_json) = event.metadata.remove(&"is-json".to_owned()) {
-        match is_json.to_lowercase().as_str() {
-            "true" => true,
-            "false" => false,
-            unknown => panic!("Unknown [{}] 'is-json' metadata value", unknown),
-        }
-    } else {
-        false
-    };
+    let metadata = SystemMetadata::parse(&mut event.metadata);
 
     let stream_id = String::from_utf8(
         event
@@ -202,8 +267,10 @@ fn convert_persistent_proto_recorded_event(
         stream_id,
         revision: event.stream_revision,
         position,
-        event_type,
-        is_json,
+        event_type: metadata.event_type,
+        is_json: metadata.is_json,
+        content_type: metadata.content_type,
+        created: metadata.created,
         metadata: event.custom_metadata.into(),
         data: event.data.into(),
     }
@@ -294,7 +361,11 @@ fn convert_proto_read_event(event: streams::read_resp::ReadEvent) -> ResolvedEve
     }
 }
 
-fn convert_persistent_proto_read_event(event: persistent::read_resp::ReadEvent) -> ResolvedEvent {
+fn convert_persistent_proto_read_event(
+    event: persistent::read_resp::ReadEvent,
+) -> PersistentSubEvent {
+    use persistent::read_resp::read_event::Count;
+
     let commit_position = if let Some(pos_alt) = event.position {
         match pos_alt {
             persistent::read_resp::read_event::Position::CommitPosition(pos) => Some(pos),
@@ -304,10 +375,246 @@ fn convert_persistent_proto_read_event(event: persistent::read_resp::ReadEvent)
         None
     };
 
-    ResolvedEvent {
-        event: event.event.map(convert_persistent_proto_recorded_event),
-        link: event.link.map(convert_persistent_proto_recorded_event),
-        commit_position,
+    let retry_count = match event.count {
+        Some(Count::RetryCount(count)) => count.max(0) as usize,
+        Some(Count::NoRetryCount(_)) | None => 0,
+    };
+
+    PersistentSubEvent {
+        inner: ResolvedEvent {
+            event: event.event.map(convert_persistent_proto_recorded_event),
+            link: event.link.map(convert_persistent_proto_recorded_event),
+            commit_position,
+        },
+        retry_count,
+    }
+}
+
+#[test]
+fn test_convert_persistent_proto_read_event_retry_count() {
+    use persistent::read_resp::read_event::Count;
+    use persistent::read_resp::ReadEvent;
+
+    fn read_event(count: Option<Count>) -> ReadEvent {
+        ReadEvent {
+            event: None,
+            link: None,
+            position: None,
+            count,
+        }
+    }
+
+    let retried = convert_persistent_proto_read_event(read_event(Some(Count::RetryCount(3))));
+    let first = convert_persistent_proto_read_event(read_event(Some(Count::NoRetryCount(
+        shared::Empty {},
+    ))));
+    let missing = convert_persistent_proto_read_event(read_event(None));
+
+    assert_eq!(retried.retry_count, 3);
+    assert_eq!(first.retry_count, 0);
+    assert_eq!(missing.retry_count, 0);
+}
+
+fn convert_proto_value(value: prost_types::Value) -> serde_json::Value {
+    use prost_types::value::Kind;
+    use serde_json::Value;
+
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(b)) => Value::Bool(b),
+        Some(Kind::StringValue(s)) => Value::String(s),
+        Some(Kind::NumberValue(n)) => {
+            // google.protobuf.Value only knows about doubles. We convert integral numbers back
+            // so they can be deserialized into integer types.
+            // `i64::MAX as f64` rounds up to 2^63, which doesn't fit in an i64.
+            if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+                Value::Number((n as i64).into())
+            } else {
+                serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+            }
+        }
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.into_iter().map(convert_proto_value).collect())
+        }
+        Some(Kind::StructValue(obj)) => Value::Object(
+            obj.fields
+                .into_iter()
+                .map(|(key, value)| (key, convert_proto_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[test]
+fn test_convert_proto_value() {
+    use prost_types::value::Kind;
+    use prost_types::{ListValue, Struct, Value};
+
+    fn value(kind: Kind) -> Value {
+        Value { kind: Some(kind) }
+    }
+
+    let mut fields = std::collections::BTreeMap::new();
+
+    fields.insert("count".to_string(), value(Kind::NumberValue(42.0)));
+    fields.insert("ratio".to_string(), value(Kind::NumberValue(0.5)));
+    fields.insert("name".to_string(), value(Kind::StringValue("foo".into())));
+    fields.insert("nothing".to_string(), value(Kind::NullValue(0)));
+    fields.insert(
+        "flags".to_string(),
+        value(Kind::ListValue(ListValue {
+            values: vec![value(Kind::BoolValue(true)), value(Kind::BoolValue(false))],
+        })),
+    );
+
+    let actual = convert_proto_value(value(Kind::StructValue(Struct { fields })));
+    let expected = serde_json::json!({
+        "count": 42,
+        "ratio": 0.5,
+        "name": "foo",
+        "nothing": null,
+        "flags": [true, false],
+    });
+
+    assert_eq!(actual, expected);
+    assert_eq!(
+        serde_json::from_value::<u64>(actual["count"].clone()).unwrap(),
+        42
+    );
+}
+
+#[test]
+fn test_convert_proto_value_i64_bounds() {
+    use prost_types::value::Kind;
+
+    fn number(n: f64) -> serde_json::Value {
+        convert_proto_value(prost_types::Value {
+            kind: Some(Kind::NumberValue(n)),
+        })
+    }
+
+    let two_pow_63 = 9_223_372_036_854_775_808f64;
+
+    assert_eq!(number(-two_pow_63), serde_json::json!(i64::MIN));
+    assert_eq!(number(two_pow_63), serde_json::json!(two_pow_63));
+    assert_eq!(number(two_pow_63).as_i64(), None);
+}
+
+fn convert_proto_projection_details(
+    details: projections::statistics_resp::Details,
+) -> ProjectionDetails {
+    let progress = if details.progress < 0.0 {
+        None
+    } else {
+        Some(details.progress)
+    };
+
+    ProjectionDetails {
+        mode: ProjectionMode::parse(details.mode.as_str()),
+        status: ProjectionStatus::parse(details.status.as_str()),
+        position: ProjectionPosition::parse(details.position.as_str()),
+        last_checkpoint: ProjectionPosition::parse(details.last_checkpoint.as_str()),
+        progress,
+        name: details.name,
+        effective_name: details.effective_name,
+        state_reason: details.state_reason,
+        version: details.version,
+        epoch: details.epoch,
+        core_processing_time: details.core_processing_time,
+        writes_in_progress: details.writes_in_progress,
+        reads_in_progress: details.reads_in_progress,
+        partitions_cached: details.partitions_cached,
+        events_processed_after_restart: details.events_processed_after_restart,
+        checkpoint_status: details.checkpoint_status,
+        buffered_events: details.buffered_events,
+        write_pending_events_before_checkpoint: details.write_pending_events_before_checkpoint,
+        write_pending_events_after_checkpoint: details.write_pending_events_after_checkpoint,
+    }
+}
+
+#[test]
+fn test_convert_proto_projection_details() {
+    let details = projections::statistics_resp::Details {
+        name: "counter".to_string(),
+        mode: "Continuous".to_string(),
+        status: "Stopped/Faulted".to_string(),
+        position: "C:1234/P:1200".to_string(),
+        last_checkpoint: "$ce-foo: 42".to_string(),
+        progress: -1.0,
+        ..Default::default()
+    };
+
+    let details = convert_proto_projection_details(details);
+
+    assert_eq!(details.mode, ProjectionMode::Continuous);
+    assert_eq!(details.status, ProjectionStatus::Faulted);
+    assert_eq!(
+        details.position,
+        Some(ProjectionPosition::All(Position {
+            commit: 1234,
+            prepare: 1200,
+        }))
+    );
+    assert_eq!(
+        details.last_checkpoint,
+        Some(ProjectionPosition::Other("$ce-foo: 42".to_string()))
+    );
+    assert_eq!(details.progress, None);
+    assert_eq!(
+        ProjectionStatus::parse("Running/Paused"),
+        ProjectionStatus::Running
+    );
+    assert_eq!(
+        ProjectionStatus::parse("Paused"),
+        ProjectionStatus::Unknown("Paused".to_string())
+    );
+    assert_eq!(ProjectionPosition::parse(""), None);
+}
+
+fn convert_proto_user_details(details: users::details_resp::UserDetails) -> UserDetails {
+    let last_updated = details
+        .last_updated
+        .and_then(|date| ticks_to_system_time(date.ticks_since_epoch));
+
+    UserDetails {
+        login: details.login_name,
+        full_name: details.full_name,
+        groups: details.groups,
+        disabled: details.disabled,
+        last_updated,
+    }
+}
+
+fn convert_proto_scavenge_resp(resp: operations::ScavengeResp) -> Option<ScavengeResult> {
+    use operations::scavenge_resp::ScavengeResult as Status;
+
+    let status = match Status::from_i32(resp.scavenge_result)? {
+        Status::Started => ScavengeStatus::Started,
+        Status::InProgress => ScavengeStatus::InProgress,
+        Status::Stopped => ScavengeStatus::Stopped,
+    };
+
+    Some(ScavengeResult {
+        id: resp.scavenge_id,
+        status,
+    })
+}
+
+// Operations can target a specific node. In that case, we don't go through
+// node selection.
+async fn execute_operation<F, Fut, A>(
+    connection: &GrpcConnection,
+    node: Option<Endpoint>,
+    action: F,
+) -> crate::Result<A>
+where
+    F: FnOnce(tonic::transport::Channel) -> Fut + Send,
+    Fut: std::future::Future<Output = Result<A, tonic::Status>> + Send,
+    A: Send,
+{
+    match node {
+        Some(node) => connection.execute_on(&node, action).await,
+        None => connection.execute(action).await,
     }
 }
 
@@ -315,23 +622,58 @@ fn configure_auth_req<A>(req: &mut Request<A>, creds_opt: Option<Credentials>) {
     use tonic::metadata::MetadataValue;
 
     if let Some(creds) = creds_opt {
-        let login = String::from_utf8_lossy(&*creds.login).into_owned();
-        let password = String::from_utf8_lossy(&*creds.password).into_owned();
-
-        let basic_auth_string = base64::encode(&format!("{}:{}", login, password));
-        let basic_auth = format!("Basic {}", basic_auth_string);
-        let header_value = MetadataValue::from_str(basic_auth.as_str())
+        let header_value = MetadataValue::from_str(basic_auth(&creds).as_str())
             .expect("Auth header value should be valid metadata header value");
 
         req.metadata_mut().insert("authorization", header_value);
     }
 }
 
+// Applies the deadline to every item of a streamed response, so a node
+// stalling in the middle of a response can't block the reader forever. The
+// stream ends after the deadline is exceeded.
+fn with_item_deadline<A>(
+    stream: Box<dyn Stream<Item = crate::Result<A>> + Send + Unpin>,
+    deadline: Option<Duration>,
+) -> Box<dyn Stream<Item = crate::Result<A>> + Send + Unpin>
+where
+    A: Send + 'static,
+{
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return stream,
+    };
+
+    let stream = stream::unfold(Some(stream), move |stream| async move {
+        let mut stream = stream?;
+
+        match tokio::time::timeout(deadline, stream.next()).await {
+            Ok(item) => item.map(|item| (item, Some(stream))),
+            Err(_) => Some((Err(crate::Error::DeadlineExceeded), None)),
+        }
+    });
+
+    Box::new(Box::pin(stream))
+}
+
+fn basic_auth(creds: &Credentials) -> String {
+    let login = String::from_utf8_lossy(&*creds.login).into_owned();
+    let password = String::from_utf8_lossy(&*creds.password).into_owned();
+
+    let basic_auth_string = base64::encode(&format!("{}:{}", login, password));
+
+    format!("Basic {}", basic_auth_string)
+}
+
+/// Server-side filter used when reading or subscribing to `$all`. Events are
+/// either filtered by stream name or by event type, using a regex or a list
+/// of prefixes.
 pub struct FilterConf {
     based_on_stream: bool,
     max: Option<u32>,
     regex: Option<String>,
     prefixes: Vec<String>,
+    checkpoint_interval_multiplier: u32,
 }
 
 impl FilterConf {
@@ -341,6 +683,7 @@ impl FilterConf {
             max: None,
             regex: None,
             prefixes: Vec::new(),
+            checkpoint_interval_multiplier: 1,
         }
     }
 
@@ -370,7 +713,16 @@ impl FilterConf {
         self
     }
 
-    pub fn into_proto(self) -> streams::read_req::options::FilterOptions {
+    /// The server sends a checkpoint every `max` times
+    /// `checkpoint_interval_multiplier` scanned events. Default: 1.
+    pub fn checkpoint_interval_multiplier(self, multiplier: u32) -> Self {
+        FilterConf {
+            checkpoint_interval_multiplier: multiplier,
+            ..self
+        }
+    }
+
+    pub(crate) fn into_proto(self) -> streams::read_req::options::FilterOptions {
         use options::filter_options::{Expression, Filter, Window};
         use streams::read_req::options::{self, FilterOptions};
 
@@ -393,7 +745,7 @@ impl FilterConf {
         FilterOptions {
             filter: Some(filter),
             window: Some(window),
-            checkpoint_interval_multiplier: 1,
+            checkpoint_interval_multiplier: self.checkpoint_interval_multiplier,
         }
     }
 }
@@ -434,16 +786,148 @@ impl WriteEvents {
         }
     }
 
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
     /// Sends asynchronously the write command to the server.
-    pub async fn send_event(
+    ///
+    /// If the expected version check fails, an `Error::WrongExpectedVersion`
+    /// is returned. Use [`send_event_checked`] to get expected version
+    /// failures as a value instead.
+    ///
+    /// [`send_event_checked`]: #method.send_event_checked
+    pub async fn send_event(self, event: EventData) -> crate::Result<WriteResult> {
+        self.send_iter(vec![event]).await
+    }
+
+    /// Sends asynchronously the write command to the server. See
+    /// [`send_event`] for how expected version failures are reported.
+    ///
+    /// [`send_event`]: #method.send_event
+    pub async fn send_iter<I>(self, events: I) -> crate::Result<WriteResult>
+    where
+        I: IntoIterator<Item = EventData> + Send + Sync,
+        <I as IntoIterator>::IntoIter: Send + Sync + 'static,
+    {
+        self.send(futures::stream::iter(events)).await
+    }
+
+    /// Sends asynchronously the write command to the server. See
+    /// [`send_event`] for how expected version failures are reported.
+    ///
+    /// [`send_event`]: #method.send_event
+    pub async fn send<S>(self, events: S) -> crate::Result<WriteResult>
+    where
+        S: Stream<Item = EventData> + Send + Sync + 'static,
+    {
+        self.send_checked(events)
+            .await?
+            .map_err(crate::Error::WrongExpectedVersion)
+    }
+
+    /// Sends the events in several appends, each one holding at most
+    /// `max_chunk_size` bytes of events, so large batches don't exceed the
+    /// server maximum append size. An event bigger than `max_chunk_size` is
+    /// sent alone.
+    ///
+    /// The first chunk is checked against the command expected version, the
+    /// following ones against the version left by the previous chunk. The
+    /// append stops at the first failing chunk, expected version failures
+    /// included, and the returned result tells how far it got.
+    ///
+    /// ```no_run
+    /// # use eventstore::{EventData, EventStoreDBConnection};
+    /// # async fn example(connection: EventStoreDBConnection, events: Vec<EventData>) {
+    /// let result = connection
+    ///     .write_events("migrated-stream")
+    ///     .send_iter_chunked(events, 512 * 1_024)
+    ///     .await;
+    ///
+    /// if let Some(error) = result.error {
+    ///     println!("Stopped after {} events: {}", result.events_written, error);
+    /// }
+    /// # }
+    /// ```
+    pub async fn send_iter_chunked<I>(self, events: I, max_chunk_size: usize) -> ChunkedWriteResult
+    where
+        I: IntoIterator<Item = EventData>,
+    {
+        let mut result = ChunkedWriteResult {
+            events_written: 0,
+            chunks_written: 0,
+            last_write: None,
+            error: None,
+        };
+
+        let mut version = self.version;
+        let mut events = events.into_iter().peekable();
+
+        while events.peek().is_some() {
+            let mut chunk = Vec::new();
+            let mut chunk_size = 0;
+
+            while let Some(event) = events.peek() {
+                let size = event.size();
+
+                if !chunk.is_empty() && chunk_size + size > max_chunk_size {
+                    break;
+                }
+
+                chunk_size += size;
+                chunk.extend(events.next());
+            }
+
+            let count = chunk.len();
+            let command = WriteEvents {
+                connection: self.connection.clone(),
+                stream: self.stream.clone(),
+                version,
+                creds: self.creds.clone(),
+            };
+
+            match command.send_iter_checked(chunk).await {
+                Ok(Ok(write)) => {
+                    version = ExpectedVersion::Exact(write.next_expected_version);
+                    result.events_written += count;
+                    result.chunks_written += 1;
+                    result.last_write = Some(write);
+                }
+
+                Ok(Err(error)) => {
+                    result.error = Some(crate::Error::WrongExpectedVersion(error));
+                    break;
+                }
+
+                Err(error) => {
+                    result.error = Some(error);
+                    break;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Like [`send_event`] but always reports expected version failures
+    /// separately, as the inner `Result`.
+    ///
+    /// [`send_event`]: #method.send_event
+    pub async fn send_event_checked(
         self,
         event: EventData,
     ) -> crate::Result<Result<WriteResult, WrongExpectedVersion>> {
-        self.send_iter(vec![event]).await
+        self.send_iter_checked(vec![event]).await
     }
 
-    /// Sends asynchronously the write command to the server.
-    pub async fn send_iter<I>(
+    /// Like [`send_iter`] but always reports expected version failures
+    /// separately, as the inner `Result`.
+    ///
+    /// [`send_iter`]: #method.send_iter
+    pub async fn send_iter_checked<I>(
         self,
         events: I,
     ) -> crate::Result<Result<WriteResult, WrongExpectedVersion>>
@@ -451,11 +935,14 @@ impl WriteEvents {
         I: IntoIterator<Item = EventData> + Send + Sync,
         <I as IntoIterator>::IntoIter: Send + Sync + 'static,
     {
-        self.send(futures::stream::iter(events)).await
+        self.send_checked(futures::stream::iter(events)).await
     }
 
-    /// Sends asynchronously the write command to the server.
-    pub async fn send<S>(
+    /// Like [`send`] but always reports expected version failures
+    /// separately, as the inner `Result`.
+    ///
+    /// [`send`]: #method.send
+    pub async fn send_checked<S>(
         self,
         events: S,
     ) -> crate::Result<Result<WriteResult, WrongExpectedVersion>>
@@ -499,20 +986,23 @@ impl WriteEvents {
                         streams::append_resp::success::CurrentRevisionOption::NoStream(_) => 0,
                     };
 
-                    let position = match success.position_option.unwrap() {
-                        streams::append_resp::success::PositionOption::Position(pos) => Position {
+                    // The server only omits the position when the events were
+                    // already written, and got deduplicated by id.
+                    let (position, deduplicated) = match success.position_option.unwrap() {
+                        streams::append_resp::success::PositionOption::Position(pos) => (Position {
                             commit: pos.commit_position,
                             prepare: pos.prepare_position,
-                        },
+                        }, false),
 
                         streams::append_resp::success::PositionOption::NoPosition(_) => {
-                            Position::start()
+                            (Position::start(), true)
                         }
                     };
 
                     let write_result = WriteResult {
                         next_expected_version,
                         position,
+                        deduplicated,
                     };
 
                     Ok(Ok(write_result))
@@ -587,6 +1077,14 @@ impl ReadStreamEvents {
         }
     }
 
+    /// Maximum amount of time to wait for the server to answer, applied to
+    /// every event streamed back. Default: the `defaultDeadline` connection
+    /// setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
     /// Performs the command with the given credentials.
     pub fn set_credentials(self, creds: Option<Credentials>) -> Self {
         ReadStreamEvents { creds, ..self }
@@ -684,6 +1182,8 @@ impl ReadStreamEvents {
 
         configure_auth_req(&mut req, self.creds);
 
+        let deadline = self.connection.deadline();
+
         self.connection
             .execute(|channel| async {
                 let mut client = StreamsClient::new(channel);
@@ -725,7 +1225,7 @@ impl ReadStreamEvents {
 
                             let stream: Box<
                                 dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin,
-                            > = Box::new(stream);
+                            > = with_item_deadline(Box::new(stream), deadline);
 
                             return Ok(ReadResult::Ok(stream));
                         }
@@ -753,6 +1253,7 @@ pub struct ReadAllEvents {
     revision: Revision<Position>,
     resolve_link_tos: bool,
     direction: ReadDirection,
+    filter: Option<FilterConf>,
     creds: Option<Credentials>,
 }
 
@@ -763,10 +1264,23 @@ impl ReadAllEvents {
             revision: Revision::Start,
             resolve_link_tos: false,
             direction: ReadDirection::Forward,
+            filter: None,
             creds,
         }
     }
 
+    /// Filters events or streams based upon a predicate. Use
+    /// [`execute_with_checkpoints`] to also know how far the server went
+    /// while looking for matching events.
+    ///
+    /// [`execute_with_checkpoints`]: #method.execute_with_checkpoints
+    pub fn filter(self, filter: FilterConf) -> Self {
+        ReadAllEvents {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
     /// Asks the command to read forward (toward the end of the stream).
     /// That's the default behavior.
     pub fn forward(self) -> Self {
@@ -790,6 +1304,14 @@ impl ReadAllEvents {
         }
     }
 
+    /// Maximum amount of time to wait for the server to answer, applied to
+    /// every event streamed back. Default: the `defaultDeadline` connection
+    /// setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
     /// Starts the read ot the given event number. By default, it starts at
     /// `types::Position::start`.
     pub fn start_from(self, start: Position) -> Self {
@@ -840,6 +1362,31 @@ impl ReadAllEvents {
         self,
         count: u64,
     ) -> crate::Result<Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin>> {
+        let stream = self
+            .execute_with_checkpoints(count)
+            .await?
+            .try_filter_map(|item| {
+                let value = match item {
+                    ReadAllItem::Event(event) => Some(*event),
+                    ReadAllItem::Checkpoint(_) => None,
+                };
+
+                futures::future::ok(value)
+            });
+
+        Ok(Box::new(stream))
+    }
+
+    /// Like [`execute`] but also emits the checkpoints sent by the server
+    /// when a filter is used. A checkpoint is the position in the
+    /// transaction file the server has reached so far, even if no event
+    /// matched the filter.
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn execute_with_checkpoints(
+        self,
+        count: u64,
+    ) -> crate::Result<Box<dyn Stream<Item = crate::Result<ReadAllItem>> + Send + Unpin>> {
         use streams::read_req::options::all_options::AllOption;
         use streams::read_req::options::{self, AllOptions, StreamOption};
         use streams::read_req::Options;
@@ -871,10 +1418,15 @@ impl ReadAllEvents {
             content: Some(options::uuid_option::Content::String(Empty {})),
         };
 
+        let filter_option = match self.filter {
+            Some(filter) => options::FilterOption::Filter(filter.into_proto()),
+            None => options::FilterOption::NoFilter(Empty {}),
+        };
+
         let options = Options {
             stream_option: Some(StreamOption::All(stream_options)),
             resolve_links: self.resolve_link_tos,
-            filter_option: Some(options::FilterOption::NoFilter(Empty {})),
+            filter_option: Some(filter_option),
             count_option: Some(options::CountOption::Count(count)),
             uuid_option: Some(uuid_option),
             read_direction,
@@ -888,6 +1440,8 @@ impl ReadAllEvents {
 
         configure_auth_req(&mut req, self.creds);
 
+        let deadline = self.connection.deadline();
+
         self.connection
             .execute(|channel| async {
                 let mut client = StreamsClient::new(channel);
@@ -895,8 +1449,14 @@ impl ReadAllEvents {
                 let stream = stream
                     .try_filter_map(|resp| {
                         let value = match resp.content.unwrap() {
-                            streams::read_resp::Content::Event(event) => {
-                                Some(convert_proto_read_event(event))
+                            streams::read_resp::Content::Event(event) => Some(ReadAllItem::Event(
+                                Box::new(convert_proto_read_event(event)),
+                            )),
+                            streams::read_resp::Content::Checkpoint(checkpoint) => {
+                                Some(ReadAllItem::Checkpoint(Position {
+                                    commit: checkpoint.commit_position,
+                                    prepare: checkpoint.prepare_position,
+                                }))
                             }
                             _ => None,
                         };
@@ -905,8 +1465,8 @@ impl ReadAllEvents {
                     })
                     .map_err(crate::Error::from_grpc);
 
-                let stream: Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin> =
-                    Box::new(stream);
+                let stream: Box<dyn Stream<Item = crate::Result<ReadAllItem>> + Send + Unpin> =
+                    with_item_deadline(Box::new(stream), deadline);
 
                 Ok(stream)
             })
@@ -921,79 +1481,234 @@ impl ReadAllEvents {
     }
 }
 
-/// Command that deletes a stream. More information on [Deleting stream and events].
-///
-/// [Deleting stream and events]: https://eventstore.org/docs/server/deleting-streams-and-events/index.html
-pub struct DeleteStream {
+/// Command that reads the metadata of a stream. The metadata are stored as
+/// the last event of the `$$stream` metastream.
+pub struct GetStreamMetadata {
     connection: GrpcConnection,
     stream: String,
-    version: ExpectedVersion,
     creds: Option<Credentials>,
-    hard_delete: bool,
 }
 
-impl DeleteStream {
+impl GetStreamMetadata {
     pub(crate) fn new(
         connection: GrpcConnection,
         stream: String,
         creds: Option<Credentials>,
     ) -> Self {
-        DeleteStream {
+        GetStreamMetadata {
             connection,
             stream,
-            hard_delete: false,
-            version: ExpectedVersion::Any,
             creds,
         }
     }
 
-    /// Asks the server to check that the stream receiving the event is at
-    /// the given expected version. Default: `ExpectedVersion::Any`.
-    pub fn expected_version(self, version: ExpectedVersion) -> Self {
-        DeleteStream { version, ..self }
-    }
-
     /// Performs the command with the given credentials.
-    pub fn credentials(self, value: Credentials) -> Self {
-        DeleteStream {
-            creds: Some(value),
+    pub fn credentials(self, creds: Credentials) -> Self {
+        GetStreamMetadata {
+            creds: Some(creds),
             ..self
         }
     }
 
-    /// Makes use of Truncate before. When a stream is deleted, its Truncate
-    /// before is set to the streams current last event number. When a soft
-    /// deleted stream is read, the read will return a StreamNotFound. After
-    /// deleting the stream, you are able to write to it again, continuing from
-    /// where it left off.
-    ///
-    /// That is the default behavior.
-    pub fn soft_delete(self) -> Self {
-        DeleteStream {
-            hard_delete: false,
-            ..self
-        }
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
     }
 
-    /// A hard delete writes a tombstone event to the stream, permanently
-    /// deleting it. The stream cannot be recreated or written to again.
-    /// Tombstone events are written with the event type '$streamDeleted'. When
-    /// a hard deleted stream is read, the read will return a StreamDeleted.
-    pub fn hard_delete(self) -> Self {
-        DeleteStream {
-            hard_delete: true,
-            ..self
-        }
-    }
+    /// Sends asynchronously the read command to the server.
+    pub async fn execute(self) -> crate::Result<StreamMetadataResult> {
+        let metastream = format!("$${}", self.stream);
+        let result = ReadStreamEvents::new(self.connection, metastream, self.creds)
+            .start_from_end_of_stream()
+            .execute(1)
+            .await;
+
+        let mut events = match result {
+            Ok(ReadResult::Ok(events)) => events,
+            Ok(ReadResult::StreamNotFound(_)) => {
+                return Ok(StreamMetadataResult::NotFound {
+                    stream: self.stream,
+                })
+            }
+            Err(crate::Error::StreamDeleted) => {
+                return Ok(StreamMetadataResult::Deleted {
+                    stream: self.stream,
+                })
+            }
+            Err(e) => return Err(e),
+        };
 
-    /// Sends asynchronously the delete command to the server.
-    pub async fn execute(self) -> crate::Result<Option<Position>> {
-        if self.hard_delete {
-            use streams::tombstone_req::options::ExpectedStreamRevision;
-            use streams::tombstone_req::Options;
-            use streams::tombstone_resp::PositionOption;
+        let event = match events.try_next().await? {
+            Some(event) => event,
+            None => {
+                return Ok(StreamMetadataResult::NotFound {
+                    stream: self.stream,
+                })
+            }
+        };
 
-            let expected_stream_revision = match self.version {
+        let event = event.get_original_event();
+        let metadata = if event.data.is_empty() {
+            StreamMetadata::default()
+        } else {
+            serde_json::from_slice(&event.data[..]).map_err(crate::Error::DeserializationError)?
+        };
+
+        let versioned = VersionedMetadata {
+            stream: self.stream,
+            version: event.revision as i64,
+            metadata,
+        };
+
+        Ok(StreamMetadataResult::Success(Box::new(versioned)))
+    }
+}
+
+/// Command that sets the metadata of a stream, by appending a `$metadata`
+/// event to the `$$stream` metastream.
+pub struct SetStreamMetadata {
+    connection: GrpcConnection,
+    stream: String,
+    metadata: StreamMetadata,
+    version: ExpectedVersion,
+    creds: Option<Credentials>,
+}
+
+impl SetStreamMetadata {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream: String,
+        metadata: StreamMetadata,
+        creds: Option<Credentials>,
+    ) -> Self {
+        SetStreamMetadata {
+            connection,
+            stream,
+            metadata,
+            version: ExpectedVersion::Any,
+            creds,
+        }
+    }
+
+    /// Asks the server to check that the metastream is at the given expected
+    /// version. Default: `ExpectedVersion::Any`.
+    pub fn expected_version(self, version: ExpectedVersion) -> Self {
+        SetStreamMetadata { version, ..self }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        SetStreamMetadata {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends asynchronously the write command to the server. Expected
+    /// version failures are reported like in [`WriteEvents::send_event`].
+    ///
+    /// [`WriteEvents::send_event`]: struct.WriteEvents.html#method.send_event
+    pub async fn execute(self) -> crate::Result<WriteResult> {
+        let metastream = format!("$${}", self.stream);
+        let event = EventData::json("$metadata", &self.metadata)
+            .expect("Stream metadata are always serializable to JSON");
+
+        WriteEvents::new(self.connection, metastream, self.creds)
+            .expected_version(self.version)
+            .send_event(event)
+            .await
+    }
+}
+
+/// Command that deletes a stream. More information on [Deleting stream and events].
+///
+/// [Deleting stream and events]: https://eventstore.org/docs/server/deleting-streams-and-events/index.html
+pub struct DeleteStream {
+    connection: GrpcConnection,
+    stream: String,
+    version: ExpectedVersion,
+    creds: Option<Credentials>,
+    hard_delete: bool,
+}
+
+impl DeleteStream {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        DeleteStream {
+            connection,
+            stream,
+            hard_delete: false,
+            version: ExpectedVersion::Any,
+            creds,
+        }
+    }
+
+    /// Asks the server to check that the stream receiving the event is at
+    /// the given expected version. Default: `ExpectedVersion::Any`.
+    pub fn expected_version(self, version: ExpectedVersion) -> Self {
+        DeleteStream { version, ..self }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, value: Credentials) -> Self {
+        DeleteStream {
+            creds: Some(value),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Makes use of Truncate before. When a stream is deleted, its Truncate
+    /// before is set to the streams current last event number. When a soft
+    /// deleted stream is read, the read will return a StreamNotFound. After
+    /// deleting the stream, you are able to write to it again, continuing from
+    /// where it left off.
+    ///
+    /// That is the default behavior.
+    pub fn soft_delete(self) -> Self {
+        DeleteStream {
+            hard_delete: false,
+            ..self
+        }
+    }
+
+    /// A hard delete writes a tombstone event to the stream, permanently
+    /// deleting it. The stream cannot be recreated or written to again.
+    /// Tombstone events are written with the event type '$streamDeleted'. When
+    /// a hard deleted stream is read, the read will return a StreamDeleted.
+    pub fn hard_delete(self) -> Self {
+        DeleteStream {
+            hard_delete: true,
+            ..self
+        }
+    }
+
+    /// Sends asynchronously the delete command to the server.
+    pub async fn execute(self) -> crate::Result<Option<Position>> {
+        if self.hard_delete {
+            use streams::tombstone_req::options::ExpectedStreamRevision;
+            use streams::tombstone_req::Options;
+            use streams::tombstone_resp::PositionOption;
+
+            let expected_stream_revision = match self.version {
                 ExpectedVersion::Any => ExpectedStreamRevision::Any(Empty {}),
                 ExpectedVersion::NoStream => ExpectedStreamRevision::NoStream(Empty {}),
                 ExpectedVersion::StreamExists => ExpectedStreamRevision::StreamExists(Empty {}),
@@ -1107,17 +1822,20 @@ impl DeleteStream {
 ///
 /// * Notes
 /// Catchup subscription are resilient to connection drops.
-/// Basically, if the connection drops. The command will restart its
-/// catching up phase from the begining and then emit a new volatile
-/// subscription request.
+/// Basically, if the connection drops, the command will resubscribe from
+/// the last event it emitted, once a new node has been selected. How many
+/// times it tries is controlled by its [`RetryPolicy`].
 ///
 /// All this process happens without the user has to do anything.
+///
+/// [`RetryPolicy`]: ../struct.RetryPolicy.html
 pub struct RegularCatchupSubscribe {
     connection: GrpcConnection,
     stream_id: String,
     resolve_link_tos: bool,
-    revision: Option<u64>,
+    revision: Revision<u64>,
     creds_opt: Option<Credentials>,
+    retry: RetryPolicy,
 }
 
 impl RegularCatchupSubscribe {
@@ -1130,8 +1848,9 @@ impl RegularCatchupSubscribe {
             connection,
             stream_id,
             resolve_link_tos: false,
-            revision: None,
+            revision: Revision::Start,
             creds_opt,
+            retry: RetryPolicy::default(),
         }
     }
 
@@ -1154,7 +1873,13 @@ impl RegularCatchupSubscribe {
     ///
     /// By default, it will start from the event number 0.
     pub fn start_position(self, start_pos: u64) -> Self {
-        let revision = Some(start_pos);
+        let revision = Revision::Exact(start_pos);
+        RegularCatchupSubscribe { revision, ..self }
+    }
+
+    /// Only delivers the events written after the subscription started.
+    pub fn start_from_end_of_stream(self) -> Self {
+        let revision = Revision::End;
         RegularCatchupSubscribe { revision, ..self }
     }
 
@@ -1166,11 +1891,17 @@ impl RegularCatchupSubscribe {
         }
     }
 
-    /// Runs the subscription command.
-    pub async fn execute(
-        self,
-    ) -> crate::Result<Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin>> {
-        use futures::future;
+    /// How the subscription resubscribes when the node it's connected to
+    /// goes away. Default: retries indefinitely every 500ms.
+    pub fn retry_policy(self, retry: RetryPolicy) -> Self {
+        RegularCatchupSubscribe { retry, ..self }
+    }
+
+    /// Runs the subscription command. The returned stream emits
+    /// [`SubEvent`]s and always ends with a `SubEvent::Dropped`.
+    ///
+    /// [`SubEvent`]: ../enum.SubEvent.html
+    pub async fn execute(self) -> crate::Result<Box<dyn Stream<Item = SubEvent> + Send + Unpin>> {
         use streams::read_req::options::stream_options::RevisionOption;
         use streams::read_req::options::{self, StreamOption, StreamOptions, SubscriptionOptions};
         use streams::read_req::Options;
@@ -1178,8 +1909,9 @@ impl RegularCatchupSubscribe {
         let read_direction = 0; // <- Going forward.
 
         let revision_option = match self.revision {
-            Some(rev) => RevisionOption::Revision(rev),
-            None => RevisionOption::Start(Empty {}),
+            Revision::Exact(rev) => RevisionOption::Revision(rev),
+            Revision::Start => RevisionOption::Start(Empty {}),
+            Revision::End => RevisionOption::End(Empty {}),
         };
 
         let stream_identifier = Some(StreamIdentifier {
@@ -1203,36 +1935,7 @@ impl RegularCatchupSubscribe {
             read_direction,
         };
 
-        let req = streams::ReadReq {
-            options: Some(options),
-        };
-
-        let mut req = Request::new(req);
-
-        configure_auth_req(&mut req, self.creds_opt);
-
-        self.connection
-            .execute(|channel| async {
-                let mut client = StreamsClient::new(channel);
-                let stream = client.read(req).await?.into_inner();
-                let stream = stream
-                    .try_filter_map(|resp| {
-                        match resp.content.unwrap() {
-                            streams::read_resp::Content::Event(event) => {
-                                future::ok(Some(convert_proto_read_event(event)))
-                            }
-                            // TODO - We might end exposing when the subscription is confirmed by the server.
-                            _ => future::ok(None),
-                        }
-                    })
-                    .map_err(crate::Error::from_grpc);
-
-                let stream: Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin> =
-                    Box::new(stream);
-
-                Ok(stream)
-            })
-            .await
+        CatchupSubscription::start(self.connection, options, self.creds_opt, self.retry).await
     }
 }
 
@@ -1240,9 +1943,10 @@ impl RegularCatchupSubscribe {
 pub struct AllCatchupSubscribe {
     connection: GrpcConnection,
     resolve_link_tos: bool,
-    revision: Option<Position>,
+    revision: Revision<Position>,
     creds_opt: Option<Credentials>,
     filter: Option<FilterConf>,
+    retry: RetryPolicy,
 }
 
 impl AllCatchupSubscribe {
@@ -1250,9 +1954,10 @@ impl AllCatchupSubscribe {
         AllCatchupSubscribe {
             connection,
             resolve_link_tos: false,
-            revision: None,
+            revision: Revision::Start,
             filter: None,
             creds_opt,
+            retry: RetryPolicy::default(),
         }
     }
 
@@ -1271,7 +1976,14 @@ impl AllCatchupSubscribe {
     /// Starting point in the transaction journal log. By default, it will start at
     /// `Revision::Start`.
     pub fn start_position(self, start_pos: Position) -> Self {
-        let revision = Some(start_pos);
+        let revision = Revision::Exact(start_pos);
+
+        AllCatchupSubscribe { revision, ..self }
+    }
+
+    /// Only delivers the events written after the subscription started.
+    pub fn start_from_end(self) -> Self {
+        let revision = Revision::End;
 
         AllCatchupSubscribe { revision, ..self }
     }
@@ -1284,6 +1996,12 @@ impl AllCatchupSubscribe {
         }
     }
 
+    /// How the subscription resubscribes when the node it's connected to
+    /// goes away. Default: retries indefinitely every 500ms.
+    pub fn retry_policy(self, retry: RetryPolicy) -> Self {
+        AllCatchupSubscribe { retry, ..self }
+    }
+
     /// Filters events or streams based upon a predicate.
     pub fn filter(self, filter: FilterConf) -> Self {
         AllCatchupSubscribe {
@@ -1295,10 +2013,13 @@ impl AllCatchupSubscribe {
     /// Preforms the catching up phase of the subscription asynchronously. When
     /// it will reach the head of stream, the command will emit a volatile
     /// subscription request.
-    pub async fn execute(
-        self,
-    ) -> crate::Result<Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin>> {
-        use futures::future;
+    ///
+    /// Like [`RegularCatchupSubscribe::execute`], the returned stream emits
+    /// [`SubEvent`]s and always ends with a `SubEvent::Dropped`.
+    ///
+    /// [`RegularCatchupSubscribe::execute`]: struct.RegularCatchupSubscribe.html#method.execute
+    /// [`SubEvent`]: ../enum.SubEvent.html
+    pub async fn execute(self) -> crate::Result<Box<dyn Stream<Item = SubEvent> + Send + Unpin>> {
         use streams::read_req::options::all_options::AllOption;
         use streams::read_req::options::{self, AllOptions, StreamOption, SubscriptionOptions};
         use streams::read_req::Options;
@@ -1306,7 +2027,7 @@ impl AllCatchupSubscribe {
         let read_direction = 0; // <- Going forward.
 
         let all_option = match self.revision {
-            Some(pos) => {
+            Revision::Exact(pos) => {
                 let pos = options::Position {
                     commit_position: pos.commit,
                     prepare_position: pos.prepare,
@@ -1315,7 +2036,8 @@ impl AllCatchupSubscribe {
                 AllOption::Position(pos)
             }
 
-            None => AllOption::Start(Empty {}),
+            Revision::Start => AllOption::Start(Empty {}),
+            Revision::End => AllOption::End(Empty {}),
         };
 
         let stream_options = AllOptions {
@@ -1340,96 +2062,479 @@ impl AllCatchupSubscribe {
             read_direction,
         };
 
-        let req = streams::ReadReq {
-            options: Some(options),
-        };
+        CatchupSubscription::start(self.connection, options, self.creds_opt, self.retry).await
+    }
+}
 
-        let mut req = Request::new(req);
+// Drives a catch-up subscription and resubscribes from the last emitted event
+// when the node goes away. A subscription starting point is exclusive so no
+// event is emitted twice. A subscription starting from the end is pinned to
+// the current last event beforehand, so resubscribing doesn't skip the
+// events written while the node was away.
+struct CatchupSubscription {
+    connection: GrpcConnection,
+    options: streams::read_req::Options,
+    creds: Option<Credentials>,
+    retry: RetryPolicy,
+    channel_id: uuid::Uuid,
+    inner: tonic::codec::Streaming<streams::ReadResp>,
+    closed: ConnectionClosed,
+    done: bool,
+}
 
-        configure_auth_req(&mut req, self.creds_opt);
+type ConnectionClosed = Box<dyn std::future::Future<Output = ()> + Send + Unpin>;
 
-        self.connection
-            .execute(|channel| async {
-                let mut client = StreamsClient::new(channel);
-                let stream = client.read(req).await?.into_inner();
-                let stream = stream
-                    .try_filter_map(|resp| {
-                        match resp.content.unwrap() {
-                            streams::read_resp::Content::Event(event) => {
-                                future::ok(Some(convert_proto_read_event(event)))
-                            }
-                            // TODO - We might end exposing when the subscription is confirmed by the server.
-                            _ => future::ok(None),
-                        }
-                    })
-                    .map_err(crate::Error::from_grpc);
+// Waits for the next message of a subscription. Returns `None` if the
+// connection gets closed first.
+async fn next_message<A>(
+    inner: &mut tonic::codec::Streaming<A>,
+    closed: &mut ConnectionClosed,
+) -> Option<Result<Option<A>, tonic::Status>> {
+    use futures::future::Either;
 
-                let stream: Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin> =
-                    Box::new(stream);
+    let message = inner.message();
 
-                Ok(stream)
-            })
-            .await
-    }
-}
+    futures::pin_mut!(message);
 
-/// A command that creates a persistent subscription for a given group.
-pub struct CreatePersistentSubscription {
-    connection: GrpcConnection,
-    stream_id: String,
-    group_name: String,
-    sub_settings: PersistentSubscriptionSettings,
-    creds: Option<Credentials>,
+    match futures::future::select(message, closed).await {
+        Either::Left((message, _)) => Some(message),
+        Either::Right(_) => None,
+    }
 }
 
-impl CreatePersistentSubscription {
-    pub(crate) fn new(
+impl CatchupSubscription {
+    async fn start(
         connection: GrpcConnection,
-        stream_id: String,
-        group_name: String,
+        options: streams::read_req::Options,
         creds: Option<Credentials>,
-    ) -> Self {
-        CreatePersistentSubscription {
+        retry: RetryPolicy,
+    ) -> crate::Result<Box<dyn Stream<Item = SubEvent> + Send + Unpin>> {
+        let mut options = options;
+
+        CatchupSubscription::pin_end(&connection, &mut options, creds.clone()).await?;
+
+        let (channel_id, inner) =
+            CatchupSubscription::subscribe(&connection, options.clone(), creds.clone()).await?;
+        let closed = Box::new(connection.closed());
+
+        let sub = CatchupSubscription {
             connection,
-            stream_id,
-            group_name,
+            options,
             creds,
-            sub_settings: PersistentSubscriptionSettings::default(),
-        }
-    }
+            retry,
+            channel_id,
+            inner,
+            closed,
+            done: false,
+        };
 
-    /// Performs the command with the given credentials.
-    pub fn credentials(self, creds: Credentials) -> Self {
-        CreatePersistentSubscription {
-            creds: Some(creds),
-            ..self
-        }
-    }
+        let stream = stream::unfold(sub, |mut sub| async move {
+            sub.next().await.map(|item| (item, sub))
+        });
 
-    /// Creates a persistent subscription based on the given
-    /// `types::PersistentSubscriptionSettings`.
-    pub fn settings(self, sub_settings: PersistentSubscriptionSettings) -> Self {
-        CreatePersistentSubscription {
-            sub_settings,
-            ..self
-        }
+        Ok(Box::new(Box::pin(stream)))
     }
 
-    /// Sends the persistent subscription creation command asynchronously to
-    /// the server.
-    pub async fn execute(self) -> crate::Result<()> {
-        use persistent::create_req::Options;
-        use persistent::CreateReq;
+    // Replaces an end of stream starting point by the current last event.
+    async fn pin_end(
+        connection: &GrpcConnection,
+        options: &mut streams::read_req::Options,
+        creds: Option<Credentials>,
+    ) -> crate::Result<()> {
+        use streams::read_req::options::all_options::AllOption;
+        use streams::read_req::options::stream_options::RevisionOption;
+        use streams::read_req::options::StreamOption;
 
-        let settings = convert_settings_create(self.sub_settings);
-        let stream_identifier = Some(StreamIdentifier {
-            stream_name: self.stream_id.into_bytes(),
-        });
-        let options = Options {
-            stream_identifier,
-            group_name: self.group_name,
-            settings: Some(settings),
-        };
+        let last = match options.stream_option.as_mut() {
+            Some(StreamOption::Stream(opts)) => {
+                if !matches!(opts.revision_option, Some(RevisionOption::End(_))) {
+                    return Ok(());
+                }
+
+                let stream = opts
+                    .stream_identifier
+                    .as_ref()
+                    .map(|id| String::from_utf8_lossy(&id.stream_name).into_owned())
+                    .unwrap_or_default();
+
+                let result = ReadStreamEvents::new(connection.clone(), stream, creds)
+                    .start_from_end_of_stream()
+                    .execute(1)
+                    .await?;
+
+                match result {
+                    ReadResult::Ok(mut events) => events.try_next().await?,
+
+                    ReadResult::StreamNotFound(_) => {
+                        opts.revision_option = Some(RevisionOption::Start(Empty {}));
+
+                        return Ok(());
+                    }
+                }
+            }
+
+            Some(StreamOption::All(opts)) => {
+                if !matches!(opts.all_option, Some(AllOption::End(_))) {
+                    return Ok(());
+                }
+
+                ReadAllEvents::new(connection.clone(), creds)
+                    .start_from_end_of_stream()
+                    .execute(1)
+                    .await?
+                    .try_next()
+                    .await?
+            }
+
+            None => return Ok(()),
+        };
+
+        match last {
+            Some(event) => resume_after(options, event.get_original_event()),
+            None => start_from_beginning(options),
+        }
+
+        Ok(())
+    }
+
+    async fn subscribe(
+        connection: &GrpcConnection,
+        options: streams::read_req::Options,
+        creds: Option<Credentials>,
+    ) -> crate::Result<(uuid::Uuid, tonic::codec::Streaming<streams::ReadResp>)> {
+        let req = streams::ReadReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, creds);
+
+        connection
+            .execute_tracked(|channel| async {
+                let mut client = StreamsClient::new(channel);
+
+                Ok(client.read(req).await?.into_inner())
+            })
+            .await
+    }
+
+    async fn next(&mut self) -> Option<SubEvent> {
+        use streams::read_resp::Content;
+
+        while !self.done {
+            let message = match next_message(&mut self.inner, &mut self.closed).await {
+                Some(message) => message,
+                None => {
+                    self.done = true;
+
+                    return Some(SubEvent::Dropped(SubscriptionDropReason::ConnectionClosed));
+                }
+            };
+
+            match message {
+                Ok(Some(resp)) => match resp.content {
+                    Some(Content::Event(event)) => {
+                        let event = convert_proto_read_event(event);
+
+                        resume_after(&mut self.options, event.get_original_event());
+
+                        return Some(SubEvent::EventAppeared(Box::new(event)));
+                    }
+
+                    Some(Content::Confirmation(confirmation)) => {
+                        return Some(SubEvent::Confirmed(confirmation.subscription_id));
+                    }
+
+                    Some(Content::Checkpoint(checkpoint)) => {
+                        let position = Position {
+                            commit: checkpoint.commit_position,
+                            prepare: checkpoint.prepare_position,
+                        };
+
+                        resume_after_position(&mut self.options, position);
+
+                        return Some(SubEvent::Checkpoint(position));
+                    }
+
+                    _ => {}
+                },
+
+                Ok(None) => {
+                    self.done = true;
+
+                    return Some(SubEvent::Dropped(SubscriptionDropReason::ServerClosed));
+                }
+
+                Err(status) => {
+                    let err = crate::Error::from_grpc(status);
+
+                    self.connection.channel_failed(self.channel_id, &err).await;
+
+                    if let Err(err) = self.resubscribe(err).await {
+                        self.done = true;
+
+                        let reason = if self.connection.is_closing() {
+                            SubscriptionDropReason::ConnectionClosed
+                        } else {
+                            SubscriptionDropReason::Error(err)
+                        };
+
+                        return Some(SubEvent::Dropped(reason));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    async fn resubscribe(&mut self, mut err: crate::Error) -> crate::Result<()> {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            if !err.is_transient() || !self.retry.limit.allows(attempts) {
+                return Err(err);
+            }
+
+            warn!(
+                "Catch-up subscription dropped: {}. Resubscribing (attempt {})",
+                err, attempts
+            );
+
+            tokio::time::delay_for(self.retry.delay).await;
+
+            match CatchupSubscription::subscribe(
+                &self.connection,
+                self.options.clone(),
+                self.creds.clone(),
+            )
+            .await
+            {
+                Ok((channel_id, inner)) => {
+                    self.channel_id = channel_id;
+                    self.inner = inner;
+
+                    return Ok(());
+                }
+
+                Err(new_err) => err = new_err,
+            }
+        }
+    }
+}
+
+// Moves a subscription starting point right after the given event.
+fn resume_after(options: &mut streams::read_req::Options, event: &RecordedEvent) {
+    use streams::read_req::options::stream_options::RevisionOption;
+    use streams::read_req::options::StreamOption;
+
+    if let Some(StreamOption::Stream(opts)) = options.stream_option.as_mut() {
+        opts.revision_option = Some(RevisionOption::Revision(event.revision));
+    }
+
+    resume_after_position(options, event.position);
+}
+
+// Only meaningful for `$all` subscriptions.
+fn resume_after_position(options: &mut streams::read_req::Options, position: Position) {
+    use streams::read_req::options::all_options::AllOption;
+    use streams::read_req::options::{self, StreamOption};
+
+    if let Some(StreamOption::All(opts)) = options.stream_option.as_mut() {
+        let pos = options::Position {
+            commit_position: position.commit,
+            prepare_position: position.prepare,
+        };
+
+        opts.all_option = Some(AllOption::Position(pos));
+    }
+}
+
+fn start_from_beginning(options: &mut streams::read_req::Options) {
+    use streams::read_req::options::all_options::AllOption;
+    use streams::read_req::options::stream_options::RevisionOption;
+    use streams::read_req::options::StreamOption;
+
+    match options.stream_option.as_mut() {
+        Some(StreamOption::Stream(opts)) => {
+            opts.revision_option = Some(RevisionOption::Start(Empty {}))
+        }
+
+        Some(StreamOption::All(opts)) => opts.all_option = Some(AllOption::Start(Empty {})),
+        None => {}
+    }
+}
+
+#[test]
+fn test_catchup_resume_after() {
+    use streams::read_req::options::all_options::AllOption;
+    use streams::read_req::options::stream_options::RevisionOption;
+    use streams::read_req::options::{self, AllOptions, StreamOption, StreamOptions};
+    use streams::read_req::Options;
+
+    fn options(stream_option: StreamOption) -> Options {
+        Options {
+            stream_option: Some(stream_option),
+            ..Default::default()
+        }
+    }
+
+    let position = Position {
+        commit: 1_200,
+        prepare: 1_100,
+    };
+    let event = RecordedEvent {
+        stream_id: "orders".to_string(),
+        id: uuid::Uuid::nil(),
+        revision: 41,
+        event_type: "order-placed".to_string(),
+        data: Default::default(),
+        metadata: Default::default(),
+        is_json: true,
+        content_type: "application/json".to_string(),
+        created: None,
+        position,
+    };
+
+    let mut stream = options(StreamOption::Stream(StreamOptions {
+        stream_identifier: None,
+        revision_option: Some(RevisionOption::End(Empty {})),
+    }));
+    let mut all = options(StreamOption::All(AllOptions {
+        all_option: Some(AllOption::End(Empty {})),
+    }));
+
+    resume_after(&mut stream, &event);
+    resume_after(&mut all, &event);
+
+    assert_eq!(
+        stream.stream_option,
+        Some(StreamOption::Stream(StreamOptions {
+            stream_identifier: None,
+            revision_option: Some(RevisionOption::Revision(41)),
+        }))
+    );
+    assert_eq!(
+        all.stream_option,
+        Some(StreamOption::All(AllOptions {
+            all_option: Some(AllOption::Position(options::Position {
+                commit_position: 1_200,
+                prepare_position: 1_100,
+            })),
+        }))
+    );
+
+    // Checkpoints only move `$all` subscriptions.
+    let checkpoint = Position {
+        commit: 2_000,
+        prepare: 1_900,
+    };
+
+    resume_after_position(&mut stream, checkpoint);
+    resume_after_position(&mut all, checkpoint);
+
+    assert_eq!(
+        stream.stream_option,
+        Some(StreamOption::Stream(StreamOptions {
+            stream_identifier: None,
+            revision_option: Some(RevisionOption::Revision(41)),
+        }))
+    );
+    assert_eq!(
+        all.stream_option,
+        Some(StreamOption::All(AllOptions {
+            all_option: Some(AllOption::Position(options::Position {
+                commit_position: 2_000,
+                prepare_position: 1_900,
+            })),
+        }))
+    );
+
+    start_from_beginning(&mut stream);
+    start_from_beginning(&mut all);
+
+    assert_eq!(
+        stream.stream_option,
+        Some(StreamOption::Stream(StreamOptions {
+            stream_identifier: None,
+            revision_option: Some(RevisionOption::Start(Empty {})),
+        }))
+    );
+    assert_eq!(
+        all.stream_option,
+        Some(StreamOption::All(AllOptions {
+            all_option: Some(AllOption::Start(Empty {})),
+        }))
+    );
+}
+
+/// A command that creates a persistent subscription for a given group.
+pub struct CreatePersistentSubscription {
+    connection: GrpcConnection,
+    stream_id: String,
+    group_name: String,
+    sub_settings: PersistentSubscriptionSettings,
+    creds: Option<Credentials>,
+}
+
+impl CreatePersistentSubscription {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream_id: String,
+        group_name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreatePersistentSubscription {
+            connection,
+            stream_id,
+            group_name,
+            creds,
+            sub_settings: PersistentSubscriptionSettings::default(),
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        CreatePersistentSubscription {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Creates a persistent subscription based on the given
+    /// `types::PersistentSubscriptionSettings`.
+    pub fn settings(self, sub_settings: PersistentSubscriptionSettings) -> Self {
+        CreatePersistentSubscription {
+            sub_settings,
+            ..self
+        }
+    }
+
+    /// Sends the persistent subscription creation command asynchronously to
+    /// the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use persistent::create_req::Options;
+        use persistent::CreateReq;
+
+        let settings = convert_settings_create(self.sub_settings);
+        let stream_identifier = Some(StreamIdentifier {
+            stream_name: self.stream_id.into_bytes(),
+        });
+        let options = Options {
+            stream_identifier,
+            group_name: self.group_name,
+            settings: Some(settings),
+        };
 
         let req = CreateReq {
             options: Some(options),
@@ -1483,6 +2588,13 @@ impl UpdatePersistentSubscription {
         }
     }
 
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
     /// Updates a persistent subscription using the given
     /// `types::PersistentSubscriptionSettings`.
     pub fn settings(self, sub_settings: PersistentSubscriptionSettings) -> Self {
@@ -1558,6 +2670,13 @@ impl DeletePersistentSubscription {
         }
     }
 
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
     /// Sends the persistent subscription deletion command asynchronously to
     /// the server.
     pub async fn execute(self) -> crate::Result<()> {
@@ -1599,6 +2718,7 @@ pub struct ConnectToPersistentSubscription {
     stream_id: String,
     group_name: String,
     batch_size: i32,
+    retry: Option<RetryPolicy>,
     creds: Option<Credentials>,
 }
 
@@ -1614,6 +2734,7 @@ impl ConnectToPersistentSubscription {
             stream_id,
             group_name,
             batch_size: 10,
+            retry: None,
             creds,
         }
     }
@@ -1631,17 +2752,28 @@ impl ConnectToPersistentSubscription {
         ConnectToPersistentSubscription { batch_size, ..self }
     }
 
+    /// Makes the subscription connect to the group again, on a newly
+    /// selected node, when the node it's connected to fails or steps down.
+    /// The same handle keeps delivering events and acknowledging them.
+    /// Events that weren't acknowledged before the reconnection are
+    /// delivered again. Default: the subscription ends with an error.
+    pub fn retry_policy(self, retry: RetryPolicy) -> Self {
+        ConnectToPersistentSubscription {
+            retry: Some(retry),
+            ..self
+        }
+    }
+
     /// Sends the persistent subscription connection request to the server
     /// asynchronously even if the subscription is available right away.
+    /// Events come with the number of times the server already tried to
+    /// deliver them. Returns separate halves to read and acknowledge events, see
+    /// [`subscribe`] for a single handle.
+    ///
+    /// [`subscribe`]: #method.subscribe
     pub async fn execute(self) -> crate::Result<(SubscriptionRead, SubscriptionWrite)> {
-        use futures::channel::mpsc;
-        use futures::sink::SinkExt;
         use persistent::read_req::options::{self, UuidOption};
-        use persistent::read_req::{self, Options};
-        use persistent::read_resp;
-        use persistent::ReadReq;
-
-        let (mut sender, recv) = mpsc::channel(500);
+        use persistent::read_req::Options;
 
         let uuid_option = UuidOption {
             content: Some(options::uuid_option::Content::String(Empty {})),
@@ -1657,98 +2789,363 @@ impl ConnectToPersistentSubscription {
             uuid_option: Some(uuid_option),
         };
 
+        PersistentSubscriptionStream::start(self.connection, options, self.creds, self.retry).await
+    }
+
+    /// Like [`execute`] but returns a single handle, which reads events and
+    /// acknowledges them.
+    ///
+    /// ```no_run
+    /// # use eventstore::EventStoreDBConnection;
+    /// # use futures::stream::TryStreamExt;
+    /// # async fn example(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+    /// let mut sub = connection
+    ///     .connect_persistent_subscription("orders", "billing")
+    ///     .subscribe()
+    ///     .await?;
+    ///
+    /// while let Some(event) = sub.try_next().await? {
+    ///     // Process the event…
+    ///     sub.ack(&event).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`execute`]: #method.execute
+    pub async fn subscribe(self) -> crate::Result<PersistentSubscription> {
+        let (read, write) = self.execute().await?;
+
+        Ok(PersistentSubscription { read, write })
+    }
+}
+
+// What the acknowledging half needs to reach the current subscription. It
+// changes every time the subscription reconnects.
+struct PersistentSession {
+    sub_id_opt: Option<String>,
+    sender: futures::channel::mpsc::Sender<persistent::ReadReq>,
+}
+
+struct PersistentSubscriptionStream {
+    connection: GrpcConnection,
+    options: persistent::read_req::Options,
+    creds: Option<Credentials>,
+    retry: Option<RetryPolicy>,
+    channel_id: uuid::Uuid,
+    inner: tonic::codec::Streaming<persistent::ReadResp>,
+    session: Arc<Mutex<PersistentSession>>,
+    closed: ConnectionClosed,
+    done: bool,
+}
+
+impl PersistentSubscriptionStream {
+    async fn start(
+        connection: GrpcConnection,
+        options: persistent::read_req::Options,
+        creds: Option<Credentials>,
+        retry: Option<RetryPolicy>,
+    ) -> crate::Result<(SubscriptionRead, SubscriptionWrite)> {
+        let (channel_id, inner, session) =
+            PersistentSubscriptionStream::subscribe(&connection, options.clone(), creds.clone())
+                .await?;
+        let closed = Box::new(connection.closed());
+        let session = Arc::new(Mutex::new(session));
+
+        let sub = PersistentSubscriptionStream {
+            connection,
+            options,
+            creds,
+            retry,
+            channel_id,
+            inner,
+            session: session.clone(),
+            closed,
+            done: false,
+        };
+
+        let stream = stream::unfold(sub, |mut sub| async move {
+            sub.next().await.map(|item| (item, sub))
+        });
+
+        let read = SubscriptionRead {
+            inner: Box::new(Box::pin(stream)),
+        };
+        let write = SubscriptionWrite { session };
+
+        Ok((read, write))
+    }
+
+    async fn subscribe(
+        connection: &GrpcConnection,
+        options: persistent::read_req::Options,
+        creds: Option<Credentials>,
+    ) -> crate::Result<(
+        uuid::Uuid,
+        tonic::codec::Streaming<persistent::ReadResp>,
+        PersistentSession,
+    )> {
+        use futures::channel::mpsc;
+        use futures::sink::SinkExt;
+        use persistent::read_req;
+        use persistent::read_resp;
+        use persistent::ReadReq;
+
+        let (mut sender, recv) = mpsc::channel(500);
         let read_req = ReadReq {
             content: Some(read_req::Content::Options(options)),
         };
 
         let mut req = Request::new(recv);
 
-        configure_auth_req(&mut req, self.creds.clone());
+        configure_auth_req(&mut req, creds);
 
         let _ = sender.send(read_req).await;
 
-        self.connection
-            .execute(|channel| async {
+        let (channel_id, (inner, sub_id_opt)) = connection
+            .execute_tracked(|channel| async {
                 let mut client = PersistentSubscriptionsClient::new(channel);
                 let mut stream = client.read(req).await?.into_inner();
                 let mut sub_id_opt = None;
 
                 if let Some(evt) = stream.try_next().await? {
-                    if let Some(content) = evt.content {
-                        if let read_resp::Content::SubscriptionConfirmation(params) = content {
-                            sub_id_opt = Some(params.subscription_id);
-                        }
+                    if let Some(read_resp::Content::SubscriptionConfirmation(params)) = evt.content
+                    {
+                        sub_id_opt = Some(params.subscription_id);
                     }
                 }
 
-                let stream = stream
-                    .try_filter_map(|resp| {
-                        let ret = match resp
-                            .content
-                            .expect("Why response content wouldn't be defined?")
-                        {
-                            read_resp::Content::Event(evt) => {
-                                Some(convert_persistent_proto_read_event(evt))
-                            }
-                            _ => None,
-                        };
-
-                        futures::future::ready(Ok(ret))
-                    })
-                    .map_err(crate::Error::from_grpc);
+                Ok((stream, sub_id_opt))
+            })
+            .await?;
 
-                let read = SubscriptionRead {
-                    inner: Box::new(stream),
-                };
-                let write = SubscriptionWrite { sub_id_opt, sender };
+        let session = PersistentSession { sub_id_opt, sender };
 
-                Ok((read, write))
-            })
-            .await
+        Ok((channel_id, inner, session))
     }
-}
 
-pub struct SubscriptionRead {
-    inner: Box<dyn Stream<Item = crate::Result<ResolvedEvent>> + Send + Unpin>,
-}
+    async fn next(&mut self) -> Option<crate::Result<PersistentSubEvent>> {
+        use persistent::read_resp::Content;
 
-impl SubscriptionRead {
-    pub async fn try_next(&mut self) -> crate::Result<Option<ResolvedEvent>> {
-        self.inner.try_next().await
-    }
-}
-fn to_proto_uuid(id: uuid::Uuid) -> Uuid {
-    Uuid {
-        value: Some(shared::uuid::Value::String(format!("{}", id))),
-    }
-}
+        while !self.done {
+            let message = match next_message(&mut self.inner, &mut self.closed).await {
+                Some(message) => message,
+                None => {
+                    self.done = true;
 
-pub struct SubscriptionWrite {
-    sub_id_opt: Option<String>,
-    sender: futures::channel::mpsc::Sender<persistent::ReadReq>,
+                    return Some(Err(crate::Error::ConnectionClosed));
+                }
+            };
+
+            match message {
+                Ok(Some(resp)) => {
+                    if let Some(Content::Event(event)) = resp.content {
+                        return Some(Ok(convert_persistent_proto_read_event(event)));
+                    }
+                }
+
+                Ok(None) => {
+                    self.done = true;
+                }
+
+                Err(status) => {
+                    let err = crate::Error::from_grpc(status);
+
+                    self.connection.channel_failed(self.channel_id, &err).await;
+
+                    let retry = match self.retry {
+                        Some(retry) => retry,
+                        None => {
+                            self.done = true;
+
+                            return Some(Err(err));
+                        }
+                    };
+
+                    if let Err(err) = self.resubscribe(err, retry).await {
+                        self.done = true;
+
+                        return Some(Err(err));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    async fn resubscribe(
+        &mut self,
+        mut err: crate::Error,
+        retry: RetryPolicy,
+    ) -> crate::Result<()> {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            if !err.is_transient() || !retry.limit.allows(attempts) {
+                return Err(err);
+            }
+
+            warn!(
+                "Persistent subscription {} dropped: {}. Reconnecting (attempt {})",
+                self.options.group_name, err, attempts
+            );
+
+            tokio::time::delay_for(retry.delay).await;
+
+            match PersistentSubscriptionStream::subscribe(
+                &self.connection,
+                self.options.clone(),
+                self.creds.clone(),
+            )
+            .await
+            {
+                Ok((channel_id, inner, session)) => {
+                    self.channel_id = channel_id;
+                    self.inner = inner;
+                    *self.session.lock().unwrap() = session;
+
+                    return Ok(());
+                }
+
+                Err(new_err) => err = new_err,
+            }
+        }
+    }
+}
+
+/// Reading half of a persistent subscription. It ends when the server
+/// closes the subscription. When the connection gets closed, it yields a
+/// last `Error::ConnectionClosed`.
+pub struct SubscriptionRead {
+    inner: Box<dyn Stream<Item = crate::Result<PersistentSubEvent>> + Send + Unpin>,
+}
+
+impl SubscriptionRead {
+    pub async fn try_next(&mut self) -> crate::Result<Option<PersistentSubEvent>> {
+        self.inner.try_next().await
+    }
+}
+
+impl Stream for SubscriptionRead {
+    type Item = crate::Result<PersistentSubEvent>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Persistent subscription handle that reads events and acknowledges them.
+/// It's a `Stream` of events.
+pub struct PersistentSubscription {
+    read: SubscriptionRead,
+    write: SubscriptionWrite,
+}
+
+impl PersistentSubscription {
+    pub async fn try_next(&mut self) -> crate::Result<Option<PersistentSubEvent>> {
+        self.read.try_next().await
+    }
+
+    /// Acknowledges the given event was processed.
+    pub async fn ack(&mut self, event: &PersistentSubEvent) -> crate::Result<()> {
+        self.write
+            .ack(vec![event.inner.get_original_event().id])
+            .await
+    }
+
+    /// Reports the given event couldn't be processed. The server handles it
+    /// according to the given action.
+    pub async fn nack<S>(
+        &mut self,
+        event: &PersistentSubEvent,
+        action: NakAction,
+        reason: S,
+    ) -> crate::Result<()>
+    where
+        S: AsRef<str>,
+    {
+        self.write
+            .nack(
+                vec![event.inner.get_original_event().id],
+                action,
+                reason.as_ref().to_string(),
+            )
+            .await
+    }
+
+    /// Splits the handle into its reading and acknowledging halves.
+    pub fn split(self) -> (SubscriptionRead, SubscriptionWrite) {
+        (self.read, self.write)
+    }
+}
+
+impl Stream for PersistentSubscription {
+    type Item = crate::Result<PersistentSubEvent>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.read.poll_next_unpin(cx)
+    }
+}
+fn to_proto_uuid(id: uuid::Uuid) -> Uuid {
+    Uuid {
+        value: Some(shared::uuid::Value::String(format!("{}", id))),
+    }
+}
+
+/// Acknowledging half of a persistent subscription. Acknowledgements fail
+/// with `Error::ConnectionClosed` once the subscription is gone, and with
+/// `Error::PersistentSubscriptionNotConfirmed` if the server never confirmed
+/// the subscription.
+pub struct SubscriptionWrite {
+    session: Arc<Mutex<PersistentSession>>,
 }
 
 impl SubscriptionWrite {
-    pub async fn ack_event(&mut self, event: ResolvedEvent) -> Result<(), tonic::Status> {
-        self.ack(vec![event.get_original_event().id]).await
+    pub async fn ack_event(&mut self, event: PersistentSubEvent) -> crate::Result<()> {
+        self.ack(vec![event.inner.get_original_event().id]).await
+    }
+
+    fn encoded_sub_id(&self) -> Option<Vec<u8>> {
+        self.session
+            .lock()
+            .unwrap()
+            .sub_id_opt
+            .as_ref()
+            .map(|id| base64::encode(id).into_bytes())
+    }
+
+    async fn send(&mut self, read_req: persistent::ReadReq) -> crate::Result<()> {
+        use futures::sink::SinkExt;
+
+        let mut sender = self.session.lock().unwrap().sender.clone();
+
+        sender
+            .send(read_req)
+            .await
+            .map_err(|_| crate::Error::ConnectionClosed)
     }
 
-    pub async fn ack<I>(&mut self, event_ids: I) -> Result<(), tonic::Status>
+    pub async fn ack<I>(&mut self, event_ids: I) -> crate::Result<()>
     where
         I: IntoIterator<Item = uuid::Uuid>,
     {
-        use futures::sink::SinkExt;
         use persistent::read_req::{Ack, Content};
         use persistent::ReadReq;
 
         let ids = event_ids.into_iter().map(to_proto_uuid).collect();
         let ack = Ack {
-            id: base64::encode(
-                self.sub_id_opt
-                    .as_ref()
-                    .expect("subscription id must be defined"),
-            )
-            .into_bytes(),
+            id: self
+                .encoded_sub_id()
+                .ok_or(crate::Error::PersistentSubscriptionNotConfirmed)?,
             ids,
         };
 
@@ -1757,9 +3154,7 @@ impl SubscriptionWrite {
             content: Some(content),
         };
 
-        let _ = self.sender.send(read_req).await;
-
-        Ok(())
+        self.send(read_req).await
     }
 
     pub async fn nack<I>(
@@ -1767,15 +3162,14 @@ impl SubscriptionWrite {
         event_ids: I,
         action: NakAction,
         reason: String,
-    ) -> Result<(), tonic::Status>
+    ) -> crate::Result<()>
     where
-        I: Iterator<Item = uuid::Uuid>,
+        I: IntoIterator<Item = uuid::Uuid>,
     {
-        use futures::sink::SinkExt;
         use persistent::read_req::{Content, Nack};
         use persistent::ReadReq;
 
-        let ids = event_ids.map(to_proto_uuid).collect();
+        let ids = event_ids.into_iter().map(to_proto_uuid).collect();
 
         let action = match action {
             NakAction::Unknown => 0,
@@ -1786,12 +3180,9 @@ impl SubscriptionWrite {
         };
 
         let nack = Nack {
-            id: base64::encode(
-                self.sub_id_opt
-                    .as_ref()
-                    .expect("subscription id must be defined"),
-            )
-            .into_bytes(),
+            id: self
+                .encoded_sub_id()
+                .ok_or(crate::Error::PersistentSubscriptionNotConfirmed)?,
             ids,
             action,
             reason,
@@ -1802,8 +3193,2654 @@ impl SubscriptionWrite {
             content: Some(content),
         };
 
-        let _ = self.sender.send(read_req).await;
+        self.send(read_req).await
+    }
+}
+
+// A node serving a single persistent subscription, one session per `Read`
+// call. The first `failing_sessions` sessions fail right after delivering
+// their event, the next ones stay up. Ack subscription ids are forwarded to
+// the test.
+#[cfg(test)]
+#[derive(Clone)]
+struct FakePersistentNode {
+    sessions: Arc<Mutex<usize>>,
+    failing_sessions: usize,
+    acks: futures::channel::mpsc::UnboundedSender<Vec<u8>>,
+}
 
-        Ok(())
+#[cfg(test)]
+type FakeReadStream = std::pin::Pin<
+    Box<dyn Stream<Item = Result<persistent::ReadResp, tonic::Status>> + Send + Sync>,
+>;
+
+#[cfg(test)]
+impl FakePersistentNode {
+    /// Starts a node and connects to it.
+    async fn start(
+        failing_sessions: usize,
+    ) -> (
+        crate::EventStoreDBConnection,
+        futures::channel::mpsc::UnboundedReceiver<Vec<u8>>,
+    ) {
+        let (acks, received_acks) = futures::channel::mpsc::unbounded();
+        let node = FakePersistentNode {
+            sessions: Arc::new(Mutex::new(0)),
+            failing_sessions,
+            acks,
+        };
+
+        let mut listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(node)
+                .serve_with_incoming(listener.incoming())
+                .await
+        });
+
+        let settings = format!("esdb://127.0.0.1:{}?tls=false", port)
+            .parse::<crate::ConnectionSettings>()
+            .unwrap();
+        let connection = crate::EventStoreDBConnection::create(settings)
+            .await
+            .unwrap();
+
+        (connection, received_acks)
+    }
+
+    fn event() -> persistent::ReadResp {
+        use persistent::read_resp::{self, read_event};
+
+        let event = read_event::RecordedEvent {
+            id: Some(to_proto_uuid(uuid::Uuid::new_v4())),
+            stream_identifier: Some(StreamIdentifier {
+                stream_name: b"orders".to_vec(),
+            }),
+            stream_revision: 0,
+            prepare_position: 0,
+            commit_position: 0,
+            metadata: HashMap::new(),
+            custom_metadata: vec![],
+            data: vec![],
+        };
+
+        persistent::ReadResp {
+            content: Some(read_resp::Content::Event(read_resp::ReadEvent {
+                event: Some(event),
+                link: None,
+                position: None,
+                count: None,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+impl tonic::server::StreamingService<persistent::ReadReq> for FakePersistentNode {
+    type Response = persistent::ReadResp;
+    type ResponseStream = FakeReadStream;
+    type Future = tonic::codegen::BoxFuture<tonic::Response<FakeReadStream>, tonic::Status>;
+
+    fn call(&mut self, req: tonic::Request<tonic::Streaming<persistent::ReadReq>>) -> Self::Future {
+        use persistent::read_req;
+        use persistent::read_resp;
+
+        let session = {
+            let mut sessions = self.sessions.lock().unwrap();
+            *sessions += 1;
+            *sessions
+        };
+
+        let acks = self.acks.clone();
+        let mut reqs = req.into_inner();
+
+        tokio::spawn(async move {
+            while let Ok(Some(req)) = reqs.message().await {
+                if let Some(read_req::Content::Ack(ack)) = req.content {
+                    let _ = acks.unbounded_send(ack.id);
+                }
+            }
+        });
+
+        let confirmation = read_resp::SubscriptionConfirmation {
+            subscription_id: format!("sub-{}", session),
+        };
+        let confirmation = persistent::ReadResp {
+            content: Some(read_resp::Content::SubscriptionConfirmation(confirmation)),
+        };
+        let resps = stream::iter(vec![Ok(confirmation), Ok(FakePersistentNode::event())]);
+        let resps: FakeReadStream = if session <= self.failing_sessions {
+            let failure = tonic::Status::unavailable("node restarting");
+            Box::pin(resps.chain(stream::iter(vec![Err(failure)])))
+        } else {
+            Box::pin(resps.chain(stream::pending()))
+        };
+
+        Box::pin(async move { Ok(tonic::Response::new(resps)) })
+    }
+}
+
+#[cfg(test)]
+impl<B> tonic::codegen::Service<http::Request<B>> for FakePersistentNode
+where
+    B: tonic::codegen::HttpBody + Send + Sync + 'static,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = std::convert::Infallible;
+    type Future = tonic::codegen::BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut tonic::codegen::Context<'_>,
+    ) -> tonic::codegen::Poll<Result<(), Self::Error>> {
+        tonic::codegen::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let node = self.clone();
+
+        Box::pin(async move {
+            let codec = tonic::codec::ProstCodec::default();
+            Ok(tonic::server::Grpc::new(codec).streaming(node, req).await)
+        })
+    }
+}
+
+#[cfg(test)]
+impl tonic::transport::NamedService for FakePersistentNode {
+    const NAME: &'static str =
+        "event_store.client.persistent_subscriptions.PersistentSubscriptions";
+}
+
+#[tokio::test]
+async fn test_persistent_subscription_resubscribes_on_channel_failure() {
+    use crate::types::Retry;
+
+    let (connection, mut received_acks) = FakePersistentNode::start(1).await;
+    let retry = RetryPolicy::new(Retry::Only(3)).delay(Duration::from_millis(10));
+    let mut sub = connection
+        .connect_persistent_subscription("orders", "billing")
+        .retry_policy(retry)
+        .subscribe()
+        .await
+        .unwrap();
+
+    let first = sub.try_next().await.unwrap().unwrap();
+    sub.ack(&first).await.unwrap();
+
+    assert_eq!(received_acks.next().await, Some(b"c3ViLTE=".to_vec()));
+
+    // The node drops the first session, the same handle keeps going on the
+    // second one and acks with its subscription id.
+    let second = sub.try_next().await.unwrap().unwrap();
+    sub.ack(&second).await.unwrap();
+
+    assert_ne!(
+        first.inner.get_original_event().id,
+        second.inner.get_original_event().id
+    );
+    assert_eq!(received_acks.next().await, Some(b"c3ViLTI=".to_vec()));
+}
+
+/// A managed persistent subscription consumer. It hands every event to an
+/// async handler, acks the events the handler processed and nacks the ones
+/// it failed on. Acks are sent in batches, once `ack_batch_size` events are
+/// processed or `ack_interval` elapsed, whichever comes first. When the
+/// subscription drops on a transient error, the worker connects to it again
+/// according to its retry policy. Retries are counted again from scratch once
+/// a session delivered an event or stayed up for 30 seconds.
+pub struct PersistentWorker {
+    connection: GrpcConnection,
+    stream_id: String,
+    group_name: String,
+    batch_size: i32,
+    concurrency: usize,
+    ack_batch_size: usize,
+    ack_interval: Duration,
+    nak_action: NakAction,
+    park_after: Option<usize>,
+    retry: RetryPolicy,
+    creds: Option<Credentials>,
+}
+
+impl PersistentWorker {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream_id: String,
+        group_name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        PersistentWorker {
+            connection,
+            stream_id,
+            group_name,
+            batch_size: 10,
+            concurrency: 1,
+            ack_batch_size: 10,
+            ack_interval: Duration::from_millis(100),
+            nak_action: NakAction::Retry,
+            park_after: None,
+            retry: RetryPolicy::default(),
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        PersistentWorker {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// The buffer size to use for the persistent subscription. It's the
+    /// maximum number of events the server sends without getting them
+    /// acknowledged. Default: 10.
+    pub fn batch_size(self, batch_size: i32) -> Self {
+        PersistentWorker { batch_size, ..self }
+    }
+
+    /// Maximum number of events handled at the same time. Default: 1.
+    pub fn concurrency(self, concurrency: usize) -> Self {
+        PersistentWorker {
+            concurrency: concurrency.max(1),
+            ..self
+        }
+    }
+
+    /// Number of processed events that triggers sending their acks.
+    /// Default: 10.
+    pub fn ack_batch_size(self, ack_batch_size: usize) -> Self {
+        PersistentWorker {
+            ack_batch_size: ack_batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time a processed event waits for its ack to be
+    /// sent. Default: 100ms.
+    pub fn ack_interval(self, ack_interval: Duration) -> Self {
+        PersistentWorker {
+            ack_interval,
+            ..self
+        }
+    }
+
+    /// What the server does with an event the handler failed on.
+    /// Default: `NakAction::Retry`.
+    pub fn nak_action(self, nak_action: NakAction) -> Self {
+        PersistentWorker { nak_action, ..self }
+    }
+
+    /// Parks an event the handler failed on once the server already retried
+    /// it that many times, whatever the `nak_action` is.
+    pub fn park_after_retries(self, retries: usize) -> Self {
+        PersistentWorker {
+            park_after: Some(retries),
+            ..self
+        }
+    }
+
+    /// How the worker connects again when the subscription drops. Default:
+    /// retries indefinitely every 500ms.
+    pub fn retry_policy(self, retry: RetryPolicy) -> Self {
+        PersistentWorker { retry, ..self }
+    }
+
+    /// Runs the worker. A handler error is used as the nack reason. It
+    /// completes once the connection is closed and fails when the
+    /// subscription can't be reached anymore. When the subscription drops,
+    /// the events already handed to the handler are still acked or nacked.
+    /// Dropping the returned future stops the worker right away.
+    ///
+    /// ```no_run
+    /// # use eventstore::EventStoreDBConnection;
+    /// # async fn example(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+    /// connection
+    ///     .persistent_worker("orders", "billing")
+    ///     .concurrency(4)
+    ///     .park_after_retries(5)
+    ///     .run(|event| async move {
+    ///         // Process `event.inner`…
+    ///         Ok::<_, String>(())
+    ///     })
+    ///     .await
+    /// # }
+    /// ```
+    pub async fn run<H, Fut, E>(self, handler: H) -> crate::Result<()>
+    where
+        H: Fn(PersistentSubEvent) -> Fut,
+        Fut: std::future::Future<Output = Result<(), E>>,
+        E: std::fmt::Display,
+    {
+        let mut attempts = 0;
+
+        loop {
+            let sub = ConnectToPersistentSubscription {
+                connection: self.connection.clone(),
+                stream_id: self.stream_id.clone(),
+                group_name: self.group_name.clone(),
+                batch_size: self.batch_size,
+                retry: None,
+                creds: self.creds.clone(),
+            };
+
+            let result = match sub.execute().await {
+                Ok((read, write)) => {
+                    let started = tokio::time::Instant::now();
+                    let mut delivered = false;
+                    let result = self.process(read, write, &handler, &mut delivered).await;
+
+                    // Only a session that actually worked counts as a
+                    // successful reconnection, otherwise a subscription
+                    // failing right after its confirmation is retried forever.
+                    if delivered || started.elapsed() >= Duration::from_secs(30) {
+                        attempts = 0;
+                    }
+
+                    result
+                }
+
+                Err(err) => Err(err),
+            };
+
+            if self.connection.is_closing() {
+                return Ok(());
+            }
+
+            if let Err(err) = result.as_ref() {
+                if !err.is_transient() {
+                    return result;
+                }
+            }
+
+            attempts += 1;
+
+            if !self.retry.limit.allows(attempts) {
+                return result;
+            }
+
+            match result {
+                Ok(()) => warn!(
+                    "Persistent subscription {}::{} closed by the server. Reconnecting (attempt {})",
+                    self.stream_id, self.group_name, attempts
+                ),
+
+                Err(err) => warn!(
+                    "Persistent subscription {}::{} dropped: {}. Reconnecting (attempt {})",
+                    self.stream_id, self.group_name, err, attempts
+                ),
+            }
+
+            tokio::time::delay_for(self.retry.delay).await;
+        }
+    }
+
+    // Handles the events of a single subscription session, until the
+    // subscription ends.
+    async fn process<H, Fut, E>(
+        &self,
+        read: SubscriptionRead,
+        mut write: SubscriptionWrite,
+        handler: &H,
+        delivered: &mut bool,
+    ) -> crate::Result<()>
+    where
+        H: Fn(PersistentSubEvent) -> Fut,
+        Fut: std::future::Future<Output = Result<(), E>>,
+        E: std::fmt::Display,
+    {
+        let mut outcomes = read
+            .map(|result| {
+                let job = result.map(|event| {
+                    let id = event.inner.get_original_event().id;
+
+                    (id, event.retry_count, handler(event))
+                });
+
+                async move {
+                    let (id, retry_count, handling) = job?;
+                    let outcome = handling.await.map_err(|e| e.to_string());
+
+                    Ok::<_, crate::Error>((id, retry_count, outcome))
+                }
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut acks = Vec::new();
+        let mut flush_at = None;
+        let mut failure = None;
+
+        loop {
+            let next = match flush_at {
+                Some(at) => match tokio::time::timeout_at(at, outcomes.next()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        flush_at = None;
+                        write.ack(acks.drain(..)).await?;
+
+                        continue;
+                    }
+                },
+
+                None => outcomes.next().await,
+            };
+
+            // The subscription ends after an error, so we keep going until
+            // the events already handed to the handler are settled.
+            let (id, retry_count, outcome) = match next {
+                Some(Ok(outcome)) => outcome,
+                Some(Err(err)) => {
+                    failure.get_or_insert(err);
+                    continue;
+                }
+                None => break,
+            };
+
+            *delivered = true;
+
+            match outcome {
+                Ok(()) => {
+                    acks.push(id);
+
+                    if acks.len() >= self.ack_batch_size {
+                        flush_at = None;
+                        write.ack(acks.drain(..)).await?;
+                    } else if flush_at.is_none() {
+                        flush_at = Some(tokio::time::Instant::now() + self.ack_interval);
+                    }
+                }
+
+                Err(reason) => {
+                    let action = match self.park_after {
+                        Some(max) if retry_count >= max => NakAction::Park,
+                        _ => self.nak_action,
+                    };
+
+                    debug!(
+                        "Handler failed on event {} (retry {}): {}. Nacking with {:?}",
+                        id, retry_count, reason, action
+                    );
+
+                    write.nack(vec![id], action, reason).await?;
+                }
+            }
+        }
+
+        if !acks.is_empty() {
+            write.ack(acks).await?;
+        }
+
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_persistent_worker_acks_in_flight_events_on_close() {
+    use futures::channel::oneshot;
+    use futures::FutureExt;
+
+    let (connection, mut received_acks) = FakePersistentNode::start(0).await;
+    let (started, handling) = oneshot::channel::<()>();
+    let (release, released) = oneshot::channel::<()>();
+    let started = Mutex::new(Some(started));
+    let released = released.shared();
+    let worker = connection.persistent_worker("orders", "billing");
+
+    let running = tokio::spawn(async move {
+        worker
+            .run(|_| {
+                let started = started.lock().unwrap().take();
+                let released = released.clone();
+
+                async move {
+                    if let Some(started) = started {
+                        let _ = started.send(());
+                    }
+
+                    let _ = released.await;
+
+                    Ok::<_, String>(())
+                }
+            })
+            .await
+    });
+
+    handling.await.unwrap();
+    connection.close().await;
+    release.send(()).unwrap();
+
+    let ack = tokio::time::timeout(Duration::from_secs(5), received_acks.next()).await;
+
+    assert!(running.await.unwrap().is_ok());
+    assert_eq!(ack, Ok(Some(b"c3ViLTE=".to_vec())));
+}
+
+/// Name of the stream the server parks the events of a persistent
+/// subscription group into.
+pub(crate) fn parked_stream_name(stream_id: &str, group_name: &str) -> String {
+    format!(
+        "$persistentsubscription-{}::{}-parked",
+        stream_id, group_name
+    )
+}
+
+/// Percent-encodes a URL path segment, keeping only unreserved characters.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+#[test]
+fn test_encode_path_segment() {
+    assert_eq!(encode_path_segment("orders-1.v2_x~"), "orders-1.v2_x~");
+    assert_eq!(encode_path_segment("$ce-order"), "%24ce-order");
+    assert_eq!(encode_path_segment("a b/c?d"), "a%20b%2Fc%3Fd");
+    assert_eq!(encode_path_segment("café"), "caf%C3%A9");
+}
+
+/// Command that counts the events parked by a persistent subscription
+/// group. Events already replayed aren't counted.
+pub struct CountParkedEvents {
+    connection: GrpcConnection,
+    stream_id: String,
+    group_name: String,
+    creds: Option<Credentials>,
+}
+
+impl CountParkedEvents {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream_id: String,
+        group_name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CountParkedEvents {
+            connection,
+            stream_id,
+            group_name,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        CountParkedEvents {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command asynchronously to the server. The count is derived
+    /// from the last revision of the parked stream and its truncation point,
+    /// the parked events aren't read.
+    pub async fn execute(self) -> crate::Result<u64> {
+        let parked = parked_stream_name(&self.stream_id, &self.group_name);
+
+        let truncate_before = match GetStreamMetadata::new(
+            self.connection.clone(),
+            parked.clone(),
+            self.creds.clone(),
+        )
+        .execute()
+        .await?
+        {
+            StreamMetadataResult::Success(metadata) => {
+                metadata.metadata.truncate_before.unwrap_or(0)
+            }
+
+            _ => 0,
+        };
+
+        let result = ReadStreamEvents::new(self.connection, parked, self.creds)
+            .backward()
+            .start_from_end_of_stream()
+            .execute(1)
+            .await?;
+
+        let mut events = match result {
+            ReadResult::Ok(events) => events,
+            ReadResult::StreamNotFound(_) => return Ok(0),
+        };
+
+        let count = match events.try_next().await? {
+            Some(last) => (last.get_original_event().revision + 1).saturating_sub(truncate_before),
+            None => 0,
+        };
+
+        Ok(count)
+    }
+}
+
+/// Command that makes a persistent subscription group deliver its parked
+/// events again. There is no gRPC call for it, the command goes through the
+/// server's HTTP admin API, on the leader.
+pub struct ReplayParkedEvents {
+    connection: GrpcConnection,
+    stream_id: String,
+    group_name: String,
+    stop_at: Option<u64>,
+    creds: Option<Credentials>,
+}
+
+impl ReplayParkedEvents {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        stream_id: String,
+        group_name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        ReplayParkedEvents {
+            connection,
+            stream_id,
+            group_name,
+            stop_at: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ReplayParkedEvents {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Only replays the given number of parked events, the oldest first.
+    /// Default: every parked event is replayed.
+    pub fn stop_at(self, count: u64) -> Self {
+        ReplayParkedEvents {
+            stop_at: Some(count),
+            ..self
+        }
+    }
+
+    /// Sends the command asynchronously to the server. Parked events can
+    /// only be replayed by the leader, when the connection is on another
+    /// node it moves to the leader before the command is sent again. Fails
+    /// with `Error::PersistentSubscriptionNotFound` if the group doesn't
+    /// exist.
+    pub async fn execute(self) -> crate::Result<()> {
+        use tonic::client::GrpcService;
+
+        let mut path = format!(
+            "/subscriptions/{}/{}/replayParked",
+            encode_path_segment(&self.stream_id),
+            encode_path_segment(&self.group_name)
+        );
+
+        if let Some(count) = self.stop_at {
+            path.push_str(&format!("?stopAt={}", count));
+        }
+
+        let mut redirected = false;
+
+        loop {
+            let req = self.request(&path);
+            let (channel_id, (status, location)) = self
+                .connection
+                .execute_tracked(|mut channel| async move {
+                    futures::future::poll_fn(|cx| channel.poll_ready(cx))
+                        .await
+                        .map_err(|e| tonic::Status::unavailable(e.to_string()))?;
+
+                    let resp = channel
+                        .call(req)
+                        .await
+                        .map_err(|e| tonic::Status::unavailable(e.to_string()))?;
+
+                    let location = resp
+                        .headers()
+                        .get(http::header::LOCATION)
+                        .and_then(|location| location.to_str().ok())
+                        .and_then(|location| location.parse::<http::Uri>().ok());
+
+                    Ok((resp.status(), location))
+                })
+                .await?;
+
+            match status {
+                status if status.is_success() => return Ok(()),
+
+                // A follower redirects to the leader. It's handled like a
+                // not-leader error on a gRPC command.
+                http::StatusCode::TEMPORARY_REDIRECT if !redirected => {
+                    let leader =
+                        location
+                            .as_ref()
+                            .and_then(redirect_endpoint)
+                            .ok_or_else(|| {
+                                crate::Error::UnexpectedResponse(
+                            "Leader redirect without a valid location when replaying parked events"
+                                .to_string(),
+                        )
+                            })?;
+
+                    self.connection
+                        .channel_failed(channel_id, &crate::Error::NotLeaderException(leader))
+                        .await;
+
+                    redirected = true;
+                }
+
+                http::StatusCode::UNAUTHORIZED => return Err(crate::Error::NotAuthenticated),
+                http::StatusCode::FORBIDDEN => return Err(crate::Error::AccessDenied),
+                http::StatusCode::NOT_FOUND => {
+                    return Err(crate::Error::PersistentSubscriptionNotFound)
+                }
+
+                status => {
+                    return Err(crate::Error::UnexpectedResponse(format!(
+                        "Unexpected HTTP status when replaying parked events: {}",
+                        status
+                    )))
+                }
+            }
+        }
+    }
+
+    fn request(&self, path: &str) -> http::Request<tonic::body::BoxBody> {
+        let mut req = http::Request::builder()
+            .method(http::Method::POST)
+            .uri(path)
+            .header(http::header::CONTENT_LENGTH, 0)
+            .header("ES-RequireLeader", "True");
+
+        if let Some(creds) = self.creds.as_ref() {
+            req = req.header(http::header::AUTHORIZATION, basic_auth(creds));
+        }
+
+        req.body(tonic::body::BoxBody::empty())
+            .expect("Replay parked request should be valid")
+    }
+}
+
+// Node a redirect points to, the HTTP API sends absolute locations.
+fn redirect_endpoint(location: &http::Uri) -> Option<Endpoint> {
+    Some(Endpoint {
+        host: location.host()?.to_string(),
+        port: location.port_u16()? as u32,
+    })
+}
+
+#[test]
+fn test_redirect_endpoint() {
+    let location = "http://10.0.0.2:2113/subscriptions/orders/billing/replayParked"
+        .parse::<http::Uri>()
+        .unwrap();
+
+    assert_eq!(
+        redirect_endpoint(&location),
+        Some(Endpoint {
+            host: "10.0.0.2".to_string(),
+            port: 2113,
+        })
+    );
+
+    let relative = "/subscriptions/orders/billing/replayParked"
+        .parse::<http::Uri>()
+        .unwrap();
+
+    assert_eq!(redirect_endpoint(&relative), None);
+}
+
+enum ProjectionCreationMode {
+    OneTime,
+    Transient(String),
+    Continuous(String),
+}
+
+/// Command that creates a projection.
+pub struct CreateProjection {
+    connection: GrpcConnection,
+    mode: ProjectionCreationMode,
+    query: String,
+    track_emitted_streams: bool,
+    creds: Option<Credentials>,
+}
+
+impl CreateProjection {
+    fn new(
+        connection: GrpcConnection,
+        mode: ProjectionCreationMode,
+        query: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreateProjection {
+            connection,
+            mode,
+            query,
+            track_emitted_streams: false,
+            creds,
+        }
+    }
+
+    pub(crate) fn one_time(
+        connection: GrpcConnection,
+        query: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreateProjection::new(connection, ProjectionCreationMode::OneTime, query, creds)
+    }
+
+    pub(crate) fn transient(
+        connection: GrpcConnection,
+        name: String,
+        query: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreateProjection::new(
+            connection,
+            ProjectionCreationMode::Transient(name),
+            query,
+            creds,
+        )
+    }
+
+    pub(crate) fn continuous(
+        connection: GrpcConnection,
+        name: String,
+        query: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreateProjection::new(
+            connection,
+            ProjectionCreationMode::Continuous(name),
+            query,
+            creds,
+        )
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        CreateProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Asks the server to keep track of the streams the projection emits to,
+    /// so they can be deleted along with the projection. Only applies to
+    /// continuous projections. Default: `false`.
+    pub fn track_emitted_streams(self, track_emitted_streams: bool) -> Self {
+        CreateProjection {
+            track_emitted_streams,
+            ..self
+        }
+    }
+
+    /// Sends the projection creation command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::create_req::options::{Continuous, Mode, Transient};
+        use projections::create_req::Options;
+
+        let mode = match self.mode {
+            ProjectionCreationMode::OneTime => Mode::OneTime(Empty {}),
+            ProjectionCreationMode::Transient(name) => Mode::Transient(Transient { name }),
+            ProjectionCreationMode::Continuous(name) => Mode::Continuous(Continuous {
+                name,
+                track_emitted_streams: self.track_emitted_streams,
+            }),
+        };
+
+        let options = Options {
+            mode: Some(mode),
+            query: self.query,
+        };
+
+        let req = projections::CreateReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.create(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that updates the query of an existing projection.
+pub struct UpdateProjection {
+    connection: GrpcConnection,
+    name: String,
+    query: String,
+    emit_enabled: Option<bool>,
+    creds: Option<Credentials>,
+}
+
+impl UpdateProjection {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        query: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        UpdateProjection {
+            connection,
+            name,
+            query,
+            emit_enabled: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        UpdateProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Allows or forbids the projection to emit events. By default, the
+    /// projection emit setting is left untouched.
+    pub fn emit_enabled(self, value: bool) -> Self {
+        UpdateProjection {
+            emit_enabled: Some(value),
+            ..self
+        }
+    }
+
+    /// Sends the projection update command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::update_req::options::EmitOption;
+        use projections::update_req::Options;
+
+        let emit_option = match self.emit_enabled {
+            Some(value) => EmitOption::EmitEnabled(value),
+            None => EmitOption::NoEmitOptions(Empty {}),
+        };
+
+        let options = Options {
+            name: self.name,
+            query: self.query,
+            emit_option: Some(emit_option),
+        };
+
+        let req = projections::UpdateReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.update(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that deletes a projection. The projection must be disabled first.
+pub struct DeleteProjection {
+    connection: GrpcConnection,
+    name: String,
+    delete_emitted_streams: bool,
+    delete_state_stream: bool,
+    delete_checkpoint_stream: bool,
+    creds: Option<Credentials>,
+}
+
+impl DeleteProjection {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        DeleteProjection {
+            connection,
+            name,
+            delete_emitted_streams: false,
+            delete_state_stream: false,
+            delete_checkpoint_stream: false,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        DeleteProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Also deletes the streams the projection emitted to. It only works if
+    /// the projection was created with emitted streams tracking enabled.
+    /// Default: `false`.
+    pub fn delete_emitted_streams(self, delete_emitted_streams: bool) -> Self {
+        DeleteProjection {
+            delete_emitted_streams,
+            ..self
+        }
+    }
+
+    /// Also deletes the projection state stream. Default: `false`.
+    pub fn delete_state_stream(self, delete_state_stream: bool) -> Self {
+        DeleteProjection {
+            delete_state_stream,
+            ..self
+        }
+    }
+
+    /// Also deletes the projection checkpoint stream. Default: `false`.
+    pub fn delete_checkpoint_stream(self, delete_checkpoint_stream: bool) -> Self {
+        DeleteProjection {
+            delete_checkpoint_stream,
+            ..self
+        }
+    }
+
+    /// Sends the projection deletion command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::delete_req::Options;
+
+        let options = Options {
+            name: self.name,
+            delete_emitted_streams: self.delete_emitted_streams,
+            delete_state_stream: self.delete_state_stream,
+            delete_checkpoint_stream: self.delete_checkpoint_stream,
+        };
+
+        let req = projections::DeleteReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.delete(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that enables a projection.
+pub struct EnableProjection {
+    connection: GrpcConnection,
+    name: String,
+    creds: Option<Credentials>,
+}
+
+impl EnableProjection {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        EnableProjection {
+            connection,
+            name,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        EnableProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the projection enabling command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::enable_req::Options;
+
+        let options = Options { name: self.name };
+        let req = projections::EnableReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.enable(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that disables a projection.
+pub struct DisableProjection {
+    connection: GrpcConnection,
+    name: String,
+    write_checkpoint: bool,
+    creds: Option<Credentials>,
+}
+
+impl DisableProjection {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        DisableProjection {
+            connection,
+            name,
+            write_checkpoint: true,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        DisableProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Writes a checkpoint before stopping the projection. Setting it to
+    /// `false` aborts the projection, which means the work done since the
+    /// last checkpoint will be redone once enabled again. Default: `true`.
+    pub fn write_checkpoint(self, write_checkpoint: bool) -> Self {
+        DisableProjection {
+            write_checkpoint,
+            ..self
+        }
+    }
+
+    /// Sends the projection disabling command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::disable_req::Options;
+
+        let options = Options {
+            name: self.name,
+            write_checkpoint: self.write_checkpoint,
+        };
+
+        let req = projections::DisableReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.disable(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that resets a projection. It restarts the projection from the
+/// beginning, discarding its current state.
+pub struct ResetProjection {
+    connection: GrpcConnection,
+    name: String,
+    write_checkpoint: bool,
+    creds: Option<Credentials>,
+}
+
+impl ResetProjection {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        ResetProjection {
+            connection,
+            name,
+            write_checkpoint: false,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ResetProjection {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Writes a checkpoint before resetting the projection. Default: `false`.
+    pub fn write_checkpoint(self, write_checkpoint: bool) -> Self {
+        ResetProjection {
+            write_checkpoint,
+            ..self
+        }
+    }
+
+    /// Sends the projection reset command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use projections::reset_req::Options;
+
+        let options = Options {
+            name: self.name,
+            write_checkpoint: self.write_checkpoint,
+        };
+
+        let req = projections::ResetReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.reset(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that restarts the projection subsystem of the server.
+pub struct RestartProjectionSubsystem {
+    connection: GrpcConnection,
+    creds: Option<Credentials>,
+}
+
+impl RestartProjectionSubsystem {
+    pub(crate) fn new(connection: GrpcConnection, creds: Option<Credentials>) -> Self {
+        RestartProjectionSubsystem { connection, creds }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        RestartProjectionSubsystem {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the projection subsystem restart command asynchronously to the
+    /// server.
+    pub async fn execute(self) -> crate::Result<()> {
+        let mut req = Request::new(Empty {});
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                client.restart_subsystem(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that reads the state of a projection.
+pub struct GetProjectionState {
+    connection: GrpcConnection,
+    name: String,
+    partition: String,
+    creds: Option<Credentials>,
+}
+
+impl GetProjectionState {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        GetProjectionState {
+            connection,
+            name,
+            partition: "".to_string(),
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        GetProjectionState {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Reads the state of a specific partition, when the projection is
+    /// partitioned (using `partitionBy` or `foreachStream` for example). By
+    /// default, the state of the whole projection is returned.
+    pub fn partition<S>(self, partition: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        GetProjectionState {
+            partition: partition.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Sends the state request asynchronously to the server and decodes the
+    /// state as a JSON value into `A`.
+    pub async fn execute<A>(self) -> crate::Result<A>
+    where
+        A: DeserializeOwned,
+    {
+        use projections::state_req::Options;
+
+        let options = Options {
+            name: self.name,
+            partition: self.partition,
+        };
+
+        let req = projections::StateReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let state = self
+            .connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                let resp = client.state(req).await?.into_inner();
+
+                Ok(resp.state)
+            })
+            .await?;
+
+        let state = state.map_or(serde_json::Value::Null, convert_proto_value);
+
+        serde_json::from_value(state).map_err(crate::Error::DeserializationError)
+    }
+}
+
+/// Command that reads the result of a projection.
+pub struct GetProjectionResult {
+    connection: GrpcConnection,
+    name: String,
+    partition: String,
+    creds: Option<Credentials>,
+}
+
+impl GetProjectionResult {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        GetProjectionResult {
+            connection,
+            name,
+            partition: "".to_string(),
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        GetProjectionResult {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Reads the result of a specific partition, when the projection is
+    /// partitioned (using `partitionBy` or `foreachStream` for example). By
+    /// default, the result of the whole projection is returned.
+    pub fn partition<S>(self, partition: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        GetProjectionResult {
+            partition: partition.as_ref().to_string(),
+            ..self
+        }
+    }
+
+    /// Sends the result request asynchronously to the server and decodes the
+    /// result as a JSON value into `A`.
+    pub async fn execute<A>(self) -> crate::Result<A>
+    where
+        A: DeserializeOwned,
+    {
+        use projections::result_req::Options;
+
+        let options = Options {
+            name: self.name,
+            partition: self.partition,
+        };
+
+        let req = projections::ResultReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let result = self
+            .connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                let resp = client.result(req).await?.into_inner();
+
+                Ok(resp.result)
+            })
+            .await?;
+
+        let result = result.map_or(serde_json::Value::Null, convert_proto_value);
+
+        serde_json::from_value(result).map_err(crate::Error::DeserializationError)
+    }
+}
+
+/// Command that streams the statistics of projections.
+pub struct ListProjections {
+    connection: GrpcConnection,
+    mode: ProjectionListMode,
+    creds: Option<Credentials>,
+}
+
+impl ListProjections {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        mode: ProjectionListMode,
+        creds: Option<Credentials>,
+    ) -> Self {
+        ListProjections {
+            connection,
+            mode,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ListProjections {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer, applied to
+    /// every projection streamed back. Default: the `defaultDeadline`
+    /// connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the statistics request asynchronously to the server.
+    pub async fn execute(
+        self,
+    ) -> crate::Result<Box<dyn Stream<Item = crate::Result<ProjectionDetails>> + Send + Unpin>>
+    {
+        use projections::statistics_req::options::Mode;
+        use projections::statistics_req::Options;
+
+        let mode = match self.mode {
+            ProjectionListMode::All => Mode::All(Empty {}),
+            ProjectionListMode::Transient => Mode::Transient(Empty {}),
+            ProjectionListMode::Continuous => Mode::Continuous(Empty {}),
+            ProjectionListMode::OneTime => Mode::OneTime(Empty {}),
+            ProjectionListMode::Name(name) => Mode::Name(name),
+        };
+
+        let req = projections::StatisticsReq {
+            options: Some(Options { mode: Some(mode) }),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let deadline = self.connection.deadline();
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = ProjectionsClient::new(channel);
+                let stream = client.statistics(req).await?.into_inner();
+                let stream = stream
+                    .try_filter_map(|resp| {
+                        futures::future::ok(resp.details.map(convert_proto_projection_details))
+                    })
+                    .map_err(crate::Error::from_grpc);
+
+                let stream: Box<
+                    dyn Stream<Item = crate::Result<ProjectionDetails>> + Send + Unpin,
+                > = with_item_deadline(Box::new(stream), deadline);
+
+                Ok(stream)
+            })
+            .await
+    }
+}
+
+/// Command that creates a user.
+pub struct CreateUser {
+    connection: GrpcConnection,
+    login: String,
+    full_name: String,
+    password: String,
+    groups: Vec<String>,
+    creds: Option<Credentials>,
+}
+
+impl CreateUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        full_name: String,
+        password: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        CreateUser {
+            connection,
+            login,
+            full_name,
+            password,
+            groups: Vec::new(),
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        CreateUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Groups the user belongs to. By default, the user doesn't belong to
+    /// any group.
+    pub fn groups<I, S>(self, groups: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        CreateUser {
+            groups: groups.into_iter().map(|g| g.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Sends the user creation command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::create_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+            password: self.password,
+            full_name: self.full_name,
+            groups: self.groups,
+        };
+
+        let req = users::CreateReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.create(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that updates the full name and the groups of a user.
+pub struct UpdateUser {
+    connection: GrpcConnection,
+    login: String,
+    full_name: String,
+    groups: Vec<String>,
+    creds: Option<Credentials>,
+}
+
+impl UpdateUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        full_name: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        UpdateUser {
+            connection,
+            login,
+            full_name,
+            groups: Vec::new(),
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        UpdateUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Groups the user belongs to. The previous groups are replaced. By
+    /// default, the user is removed from all its groups.
+    pub fn groups<I, S>(self, groups: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        UpdateUser {
+            groups: groups.into_iter().map(|g| g.as_ref().to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Sends the user update command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::update_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+            password: "".to_string(),
+            full_name: self.full_name,
+            groups: self.groups,
+        };
+
+        let req = users::UpdateReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.update(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that deletes a user.
+pub struct DeleteUser {
+    connection: GrpcConnection,
+    login: String,
+    creds: Option<Credentials>,
+}
+
+impl DeleteUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        DeleteUser {
+            connection,
+            login,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        DeleteUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the user deletion command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::delete_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+        };
+
+        let req = users::DeleteReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.delete(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that enables a user.
+pub struct EnableUser {
+    connection: GrpcConnection,
+    login: String,
+    creds: Option<Credentials>,
+}
+
+impl EnableUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        EnableUser {
+            connection,
+            login,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        EnableUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the user enabling command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::enable_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+        };
+
+        let req = users::EnableReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.enable(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that disables a user. A disabled user can't authenticate anymore.
+pub struct DisableUser {
+    connection: GrpcConnection,
+    login: String,
+    creds: Option<Credentials>,
+}
+
+impl DisableUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        DisableUser {
+            connection,
+            login,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        DisableUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the user disabling command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::disable_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+        };
+
+        let req = users::DisableReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.disable(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that reads the details of a user.
+pub struct GetUser {
+    connection: GrpcConnection,
+    login: String,
+    creds: Option<Credentials>,
+}
+
+impl GetUser {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        GetUser {
+            connection,
+            login,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        GetUser {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the user details request asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<Option<UserDetails>> {
+        use users::details_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+        };
+
+        let req = users::DetailsReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                let mut stream = client.details(req).await?.into_inner();
+
+                while let Some(resp) = stream.try_next().await? {
+                    if let Some(details) = resp.user_details {
+                        return Ok(Some(convert_proto_user_details(details)));
+                    }
+                }
+
+                Ok(None)
+            })
+            .await
+    }
+}
+
+/// Command that streams the details of every user.
+pub struct ListUsers {
+    connection: GrpcConnection,
+    creds: Option<Credentials>,
+}
+
+impl ListUsers {
+    pub(crate) fn new(connection: GrpcConnection, creds: Option<Credentials>) -> Self {
+        ListUsers { connection, creds }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ListUsers {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer, applied to
+    /// every user streamed back. Default: the `defaultDeadline` connection
+    /// setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the users details request asynchronously to the server.
+    pub async fn execute(
+        self,
+    ) -> crate::Result<Box<dyn Stream<Item = crate::Result<UserDetails>> + Send + Unpin>> {
+        // Not providing a login name means listing all the users.
+        let req = users::DetailsReq { options: None };
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let deadline = self.connection.deadline();
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                let stream = client.details(req).await?.into_inner();
+                let stream = stream
+                    .try_filter_map(|resp| {
+                        futures::future::ok(resp.user_details.map(convert_proto_user_details))
+                    })
+                    .map_err(crate::Error::from_grpc);
+
+                let stream: Box<dyn Stream<Item = crate::Result<UserDetails>> + Send + Unpin> =
+                    with_item_deadline(Box::new(stream), deadline);
+
+                Ok(stream)
+            })
+            .await
+    }
+}
+
+/// Command that changes the password of a user, knowing its current
+/// password.
+pub struct ChangeUserPassword {
+    connection: GrpcConnection,
+    login: String,
+    current_password: String,
+    new_password: String,
+    creds: Option<Credentials>,
+}
+
+impl ChangeUserPassword {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        current_password: String,
+        new_password: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        ChangeUserPassword {
+            connection,
+            login,
+            current_password,
+            new_password,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ChangeUserPassword {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the password change command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::change_password_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+            current_password: self.current_password,
+            new_password: self.new_password,
+        };
+
+        let req = users::ChangePasswordReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.change_password(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that resets the password of a user. Unlike
+/// [`ChangeUserPassword`], it doesn't require the current password but
+/// requires administrator privileges.
+///
+/// [`ChangeUserPassword`]: struct.ChangeUserPassword.html
+pub struct ResetUserPassword {
+    connection: GrpcConnection,
+    login: String,
+    new_password: String,
+    creds: Option<Credentials>,
+}
+
+impl ResetUserPassword {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        login: String,
+        new_password: String,
+        creds: Option<Credentials>,
+    ) -> Self {
+        ResetUserPassword {
+            connection,
+            login,
+            new_password,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ResetUserPassword {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the password reset command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        use users::reset_password_req::Options;
+
+        let options = Options {
+            login_name: self.login,
+            new_password: self.new_password,
+        };
+
+        let req = users::ResetPasswordReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        self.connection
+            .execute(|channel| async {
+                let mut client = UsersClient::new(channel);
+                client.reset_password(req).await?;
+
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Command that starts a scavenge on a node.
+pub struct StartScavenge {
+    connection: GrpcConnection,
+    thread_count: i32,
+    start_from_chunk: i32,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl StartScavenge {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        thread_count: i32,
+        start_from_chunk: i32,
+        creds: Option<Credentials>,
+    ) -> Self {
+        StartScavenge {
+            connection,
+            thread_count,
+            start_from_chunk,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        StartScavenge {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        StartScavenge {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the scavenge starting command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<ScavengeResult> {
+        use operations::start_scavenge_req::Options;
+
+        let options = Options {
+            thread_count: self.thread_count,
+            start_from_chunk: self.start_from_chunk,
+        };
+
+        let req = operations::StartScavengeReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let resp = execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+
+            Ok(client.start_scavenge(req).await?.into_inner())
+        })
+        .await?;
+        let result = resp.scavenge_result;
+
+        convert_proto_scavenge_resp(resp).ok_or_else(|| {
+            crate::Error::UnexpectedResponse(format!("unknown scavenge result {}", result))
+        })
+    }
+}
+
+/// Command that stops a running scavenge.
+pub struct StopScavenge {
+    connection: GrpcConnection,
+    id: String,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl StopScavenge {
+    pub(crate) fn new(connection: GrpcConnection, id: String, creds: Option<Credentials>) -> Self {
+        StopScavenge {
+            connection,
+            id,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        StopScavenge {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        StopScavenge {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the scavenge stopping command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<ScavengeResult> {
+        use operations::stop_scavenge_req::Options;
+
+        let options = Options {
+            scavenge_id: self.id,
+        };
+
+        let req = operations::StopScavengeReq {
+            options: Some(options),
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        let resp = execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+
+            Ok(client.stop_scavenge(req).await?.into_inner())
+        })
+        .await?;
+        let result = resp.scavenge_result;
+
+        convert_proto_scavenge_resp(resp).ok_or_else(|| {
+            crate::Error::UnexpectedResponse(format!("unknown scavenge result {}", result))
+        })
+    }
+}
+
+/// Command that shuts a node down.
+pub struct Shutdown {
+    connection: GrpcConnection,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl Shutdown {
+    pub(crate) fn new(connection: GrpcConnection, creds: Option<Credentials>) -> Self {
+        Shutdown {
+            connection,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        Shutdown {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        Shutdown {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the shutdown command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        let mut req = Request::new(Empty {});
+
+        configure_auth_req(&mut req, self.creds);
+
+        execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+            client.shutdown(req).await?;
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Command that merges the indexes of a node.
+pub struct MergeIndexes {
+    connection: GrpcConnection,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl MergeIndexes {
+    pub(crate) fn new(connection: GrpcConnection, creds: Option<Credentials>) -> Self {
+        MergeIndexes {
+            connection,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        MergeIndexes {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        MergeIndexes {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the index merging command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        let mut req = Request::new(Empty {});
+
+        configure_auth_req(&mut req, self.creds);
+
+        execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+            client.merge_indexes(req).await?;
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Command that asks a node to resign from its leadership, triggering an
+/// election.
+pub struct ResignNode {
+    connection: GrpcConnection,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl ResignNode {
+    pub(crate) fn new(connection: GrpcConnection, creds: Option<Credentials>) -> Self {
+        ResignNode {
+            connection,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        ResignNode {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        ResignNode {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the resignation command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        let mut req = Request::new(Empty {});
+
+        configure_auth_req(&mut req, self.creds);
+
+        execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+            client.resign_node(req).await?;
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Command that sets the priority of a node during elections. The higher
+/// the priority, the more likely the node is elected leader.
+pub struct SetNodePriority {
+    connection: GrpcConnection,
+    priority: i32,
+    node: Option<Endpoint>,
+    creds: Option<Credentials>,
+}
+
+impl SetNodePriority {
+    pub(crate) fn new(
+        connection: GrpcConnection,
+        priority: i32,
+        creds: Option<Credentials>,
+    ) -> Self {
+        SetNodePriority {
+            connection,
+            priority,
+            node: None,
+            creds,
+        }
+    }
+
+    /// Performs the command with the given credentials.
+    pub fn credentials(self, creds: Credentials) -> Self {
+        SetNodePriority {
+            creds: Some(creds),
+            ..self
+        }
+    }
+
+    /// Maximum amount of time to wait for the server to answer. Default: the
+    /// `defaultDeadline` connection setting.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.connection.set_deadline(deadline);
+        self
+    }
+
+    /// Sends the command directly to the given node instead of the node
+    /// picked by node selection.
+    pub fn node(self, node: Endpoint) -> Self {
+        SetNodePriority {
+            node: Some(node),
+            ..self
+        }
+    }
+
+    /// Sends the node priority command asynchronously to the server.
+    pub async fn execute(self) -> crate::Result<()> {
+        let req = operations::SetNodePriorityReq {
+            priority: self.priority,
+        };
+
+        let mut req = Request::new(req);
+
+        configure_auth_req(&mut req, self.creds);
+
+        execute_operation(&self.connection, self.node, |channel| async {
+            let mut client = OperationsClient::new(channel);
+            client.set_node_priority(req).await?;
+
+            Ok(())
+        })
+        .await
     }
 }
@@ -1,5 +1,8 @@
 use crate::commands;
 use crate::grpc_connection::{ConnectionSettings, GrpcConnection};
+use crate::types::{ConnectionEvent, LinkTos, ProjectionListMode, StreamMetadata};
+use futures::Stream;
+use std::time::Duration;
 
 /// Represents a connection to a single node. `EventStoreDBConnection` maintains a full duplex
 /// connection to the EventStore server. An EventStore connection operates
@@ -29,6 +32,48 @@ impl EventStoreDBConnection {
             settings,
         })
     }
+
+    /// Returns a stream of the connection state changes, like node selection
+    /// or disconnection, happening from now on. Every call returns an
+    /// independent stream.
+    ///
+    /// ```no_run
+    /// # use eventstore::{ConnectionEvent, EventStoreDBConnection};
+    /// # use futures::stream::StreamExt;
+    /// # async fn example(connection: EventStoreDBConnection) {
+    /// let mut changes = connection.state_changes();
+    ///
+    /// while let Some(event) = changes.next().await {
+    ///     if let ConnectionEvent::NodeReselected { previous, current } = event {
+    ///         println!("Moved from {:?} to {:?}", previous, current);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn state_changes(&self) -> Box<dyn Stream<Item = ConnectionEvent> + Send + Unpin> {
+        Box::new(self.connection.state_changes())
+    }
+
+    /// Closes the connection and all its clones. New commands fail with
+    /// `Error::ConnectionClosed`, catch-up and persistent subscriptions end,
+    /// and commands already sent get 5 seconds to complete before being
+    /// aborted. Completes once the connection background task has exited.
+    ///
+    /// ```no_run
+    /// # use eventstore::EventStoreDBConnection;
+    /// # async fn example(connection: EventStoreDBConnection) {
+    /// connection.close().await;
+    /// # }
+    /// ```
+    pub async fn close(&self) {
+        self.close_with_grace_period(Duration::from_secs(5)).await
+    }
+
+    /// Like `close` but with a custom grace period for the commands already
+    /// sent.
+    pub async fn close_with_grace_period(&self, grace_period: Duration) {
+        self.connection.close(grace_period).await
+    }
     /// Sends events to a given stream.
     pub fn write_events<S>(&self, stream: S) -> commands::WriteEvents
     where
@@ -79,6 +124,56 @@ impl EventStoreDBConnection {
         )
     }
 
+    /// Reads the metadata of a stream. Stream metadata are stored as the
+    /// last event of the `$$stream` metastream.
+    pub fn get_stream_metadata<S>(&self, stream: S) -> commands::GetStreamMetadata
+    where
+        S: AsRef<str>,
+    {
+        commands::GetStreamMetadata::new(
+            self.connection.clone(),
+            stream.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Sets the metadata of a stream. Previous metadata are replaced. The
+    /// expected version of the command applies to the metastream, not to the
+    /// stream itself.
+    ///
+    /// ```no_run
+    /// # use eventstore::{EventStoreDBConnection, ExpectedVersion, StreamMetadata};
+    /// # use std::time::Duration;
+    /// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+    /// let metadata = StreamMetadata::builder()
+    ///     .max_age(Duration::from_secs(3_600))
+    ///     .max_count(1_000)
+    ///     .build();
+    ///
+    /// let _ = connection
+    ///     .set_stream_metadata("orders", metadata)
+    ///     .expected_version(ExpectedVersion::NoStream)
+    ///     .execute()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_stream_metadata<S>(
+        &self,
+        stream: S,
+        metadata: StreamMetadata,
+    ) -> commands::SetStreamMetadata
+    where
+        S: AsRef<str>,
+    {
+        commands::SetStreamMetadata::new(
+            self.connection.clone(),
+            stream.as_ref().to_string(),
+            metadata,
+            self.settings.default_user_name.clone(),
+        )
+    }
+
     /// Subscribes to a given stream. This kind of subscription specifies a
     /// starting point (by default, the beginning of a stream). For a regular
     /// stream, that starting point will be an event number. For the system
@@ -186,4 +281,543 @@ impl EventStoreDBConnection {
             self.settings.default_user_name.clone(),
         )
     }
+
+    /// Runs a managed consumer of a persistent subscription group on a
+    /// stream, see `commands::PersistentWorker`.
+    pub fn persistent_worker<S>(&self, stream_id: S, group_name: S) -> commands::PersistentWorker
+    where
+        S: AsRef<str>,
+    {
+        commands::PersistentWorker::new(
+            self.connection.clone(),
+            stream_id.as_ref().to_string(),
+            group_name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Reads the events a persistent subscription group parked, see
+    /// `NakAction::Park`. Parked events are links to the original events,
+    /// which are resolved by default.
+    pub fn read_parked_events<S>(&self, stream_id: S, group_name: S) -> commands::ReadStreamEvents
+    where
+        S: AsRef<str>,
+    {
+        commands::ReadStreamEvents::new(
+            self.connection.clone(),
+            commands::parked_stream_name(stream_id.as_ref(), group_name.as_ref()),
+            self.settings.default_user_name.clone(),
+        )
+        .resolve_link_tos(LinkTos::ResolveLink)
+    }
+
+    /// Counts the events a persistent subscription group parked.
+    pub fn count_parked_events<S>(&self, stream_id: S, group_name: S) -> commands::CountParkedEvents
+    where
+        S: AsRef<str>,
+    {
+        commands::CountParkedEvents::new(
+            self.connection.clone(),
+            stream_id.as_ref().to_string(),
+            group_name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Makes a persistent subscription group deliver its parked events
+    /// again.
+    pub fn replay_parked_events<S>(
+        &self,
+        stream_id: S,
+        group_name: S,
+    ) -> commands::ReplayParkedEvents
+    where
+        S: AsRef<str>,
+    {
+        commands::ReplayParkedEvents::new(
+            self.connection.clone(),
+            stream_id.as_ref().to_string(),
+            group_name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Returns a client dedicated to projections management.
+    pub fn projections(&self) -> ProjectionManagementClient {
+        ProjectionManagementClient {
+            connection: self.connection.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+
+    /// Returns a client dedicated to server operations, like scavenging.
+    pub fn operations(&self) -> OperationsClient {
+        OperationsClient {
+            connection: self.connection.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+
+    /// Returns a client dedicated to users management.
+    pub fn users(&self) -> UserManagementClient {
+        UserManagementClient {
+            connection: self.connection.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+/// Gathers every projection management operation. A `ProjectionManagementClient`
+/// shares the underlying connection of the `EventStoreDBConnection` it has
+/// been created from.
+///
+/// ```no_run
+/// # use eventstore::EventStoreDBConnection;
+/// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+/// let query = "fromAll().when({ $init: function() { return { count: 0 }; } })";
+///
+/// connection
+///     .projections()
+///     .create_continuous_projection("counter", query)
+///     .track_emitted_streams(true)
+///     .execute()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProjectionManagementClient {
+    connection: GrpcConnection,
+    settings: ConnectionSettings,
+}
+
+impl ProjectionManagementClient {
+    /// Creates a one-time projection. A one-time projection runs until it
+    /// reaches the end of its source and stops. The server generates its
+    /// name.
+    pub fn create_one_time_projection<Q>(&self, query: Q) -> commands::CreateProjection
+    where
+        Q: AsRef<str>,
+    {
+        commands::CreateProjection::one_time(
+            self.connection.clone(),
+            query.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Creates a transient projection. A transient projection is not
+    /// persisted and is gone when the server restarts.
+    pub fn create_transient_projection<S, Q>(&self, name: S, query: Q) -> commands::CreateProjection
+    where
+        S: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        commands::CreateProjection::transient(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            query.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Creates a continuous projection. A continuous projection keeps
+    /// processing new events once it caught up with its source.
+    pub fn create_continuous_projection<S, Q>(
+        &self,
+        name: S,
+        query: Q,
+    ) -> commands::CreateProjection
+    where
+        S: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        commands::CreateProjection::continuous(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            query.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Updates the query of a projection.
+    pub fn update_projection<S, Q>(&self, name: S, query: Q) -> commands::UpdateProjection
+    where
+        S: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        commands::UpdateProjection::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            query.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Deletes a projection.
+    pub fn delete_projection<S>(&self, name: S) -> commands::DeleteProjection
+    where
+        S: AsRef<str>,
+    {
+        commands::DeleteProjection::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Enables a projection.
+    pub fn enable_projection<S>(&self, name: S) -> commands::EnableProjection
+    where
+        S: AsRef<str>,
+    {
+        commands::EnableProjection::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Disables a projection.
+    pub fn disable_projection<S>(&self, name: S) -> commands::DisableProjection
+    where
+        S: AsRef<str>,
+    {
+        commands::DisableProjection::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Resets a projection.
+    pub fn reset_projection<S>(&self, name: S) -> commands::ResetProjection
+    where
+        S: AsRef<str>,
+    {
+        commands::ResetProjection::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Reads the state of a projection. The state is decoded from JSON into
+    /// any type implementing `serde::de::DeserializeOwned`.
+    ///
+    /// ```no_run
+    /// # use eventstore::EventStoreDBConnection;
+    /// # use std::collections::HashMap;
+    /// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+    /// let state = connection
+    ///     .projections()
+    ///     .get_projection_state("counter")
+    ///     .partition("customer-42")
+    ///     .execute::<HashMap<String, u64>>()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_projection_state<S>(&self, name: S) -> commands::GetProjectionState
+    where
+        S: AsRef<str>,
+    {
+        commands::GetProjectionState::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Reads the result of a projection. Like [`get_projection_state`], the
+    /// result is decoded from JSON.
+    ///
+    /// [`get_projection_state`]: #method.get_projection_state
+    pub fn get_projection_result<S>(&self, name: S) -> commands::GetProjectionResult
+    where
+        S: AsRef<str>,
+    {
+        commands::GetProjectionResult::new(
+            self.connection.clone(),
+            name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Streams the statistics of the projections selected by `mode`.
+    ///
+    /// ```no_run
+    /// # use eventstore::{EventStoreDBConnection, ProjectionListMode};
+    /// # use futures::TryStreamExt;
+    /// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+    /// let mut stream = connection
+    ///     .projections()
+    ///     .list_projections(ProjectionListMode::Continuous)
+    ///     .execute()
+    ///     .await?;
+    ///
+    /// while let Some(details) = stream.try_next().await? {
+    ///     println!("{}: {:?} {:?}", details.name, details.status, details.progress);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_projections(&self, mode: ProjectionListMode) -> commands::ListProjections {
+        commands::ListProjections::new(
+            self.connection.clone(),
+            mode,
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Restarts the projection subsystem of the server.
+    pub fn restart_projection_subsystem(&self) -> commands::RestartProjectionSubsystem {
+        commands::RestartProjectionSubsystem::new(
+            self.connection.clone(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+}
+
+/// Gathers every user management operation. A `UserManagementClient` shares
+/// the underlying connection of the `EventStoreDBConnection` it has been
+/// created from.
+///
+/// ```no_run
+/// # use eventstore::EventStoreDBConnection;
+/// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+/// let users = connection.users();
+///
+/// users
+///     .create_user("jdoe", "John Doe", "changeit")
+///     .groups(vec!["tenant-42"])
+///     .execute()
+///     .await?;
+///
+/// users
+///     .reset_password("jdoe", "rotated")
+///     .execute()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct UserManagementClient {
+    connection: GrpcConnection,
+    settings: ConnectionSettings,
+}
+
+impl UserManagementClient {
+    /// Creates a user.
+    pub fn create_user<L, F, P>(&self, login: L, full_name: F, password: P) -> commands::CreateUser
+    where
+        L: AsRef<str>,
+        F: AsRef<str>,
+        P: AsRef<str>,
+    {
+        commands::CreateUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            full_name.as_ref().to_string(),
+            password.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Updates the full name and the groups of a user.
+    pub fn update_user<L, F>(&self, login: L, full_name: F) -> commands::UpdateUser
+    where
+        L: AsRef<str>,
+        F: AsRef<str>,
+    {
+        commands::UpdateUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            full_name.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Deletes a user.
+    pub fn delete_user<L>(&self, login: L) -> commands::DeleteUser
+    where
+        L: AsRef<str>,
+    {
+        commands::DeleteUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Enables a user.
+    pub fn enable_user<L>(&self, login: L) -> commands::EnableUser
+    where
+        L: AsRef<str>,
+    {
+        commands::EnableUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Disables a user.
+    pub fn disable_user<L>(&self, login: L) -> commands::DisableUser
+    where
+        L: AsRef<str>,
+    {
+        commands::DisableUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Reads the details of a user.
+    pub fn get_user<L>(&self, login: L) -> commands::GetUser
+    where
+        L: AsRef<str>,
+    {
+        commands::GetUser::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Streams the details of every user.
+    pub fn list_users(&self) -> commands::ListUsers {
+        commands::ListUsers::new(
+            self.connection.clone(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Changes the password of a user, knowing its current password.
+    pub fn change_password<L, C, N>(
+        &self,
+        login: L,
+        current_password: C,
+        new_password: N,
+    ) -> commands::ChangeUserPassword
+    where
+        L: AsRef<str>,
+        C: AsRef<str>,
+        N: AsRef<str>,
+    {
+        commands::ChangeUserPassword::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            current_password.as_ref().to_string(),
+            new_password.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Resets the password of a user.
+    pub fn reset_password<L, N>(&self, login: L, new_password: N) -> commands::ResetUserPassword
+    where
+        L: AsRef<str>,
+        N: AsRef<str>,
+    {
+        commands::ResetUserPassword::new(
+            self.connection.clone(),
+            login.as_ref().to_string(),
+            new_password.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+}
+
+/// Gathers every server operation. An `OperationsClient` shares the
+/// underlying connection of the `EventStoreDBConnection` it has been created
+/// from. Every command can target a specific node with its `node` method,
+/// bypassing node selection.
+///
+/// ```no_run
+/// # use eventstore::{EventStoreDBConnection, Endpoint};
+/// # async fn doc(connection: EventStoreDBConnection) -> eventstore::Result<()> {
+/// let node = Endpoint {
+///     host: "node1.esdb.local".to_string(),
+///     port: 2113,
+/// };
+///
+/// let result = connection
+///     .operations()
+///     .start_scavenge(1, 0)
+///     .node(node)
+///     .execute()
+///     .await?;
+///
+/// println!("Scavenge {} is {:?}", result.id, result.status);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct OperationsClient {
+    connection: GrpcConnection,
+    settings: ConnectionSettings,
+}
+
+impl OperationsClient {
+    /// Starts a scavenge, using `thread_count` threads and starting from
+    /// the chunk number `start_from_chunk`.
+    pub fn start_scavenge(
+        &self,
+        thread_count: i32,
+        start_from_chunk: i32,
+    ) -> commands::StartScavenge {
+        commands::StartScavenge::new(
+            self.connection.clone(),
+            thread_count,
+            start_from_chunk,
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Stops the scavenge with the given id.
+    pub fn stop_scavenge<S>(&self, id: S) -> commands::StopScavenge
+    where
+        S: AsRef<str>,
+    {
+        commands::StopScavenge::new(
+            self.connection.clone(),
+            id.as_ref().to_string(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Shuts a node down.
+    pub fn shutdown(&self) -> commands::Shutdown {
+        commands::Shutdown::new(
+            self.connection.clone(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Merges the indexes of a node.
+    pub fn merge_indexes(&self) -> commands::MergeIndexes {
+        commands::MergeIndexes::new(
+            self.connection.clone(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Asks a node to resign from its leadership.
+    pub fn resign_node(&self) -> commands::ResignNode {
+        commands::ResignNode::new(
+            self.connection.clone(),
+            self.settings.default_user_name.clone(),
+        )
+    }
+
+    /// Sets the election priority of a node.
+    pub fn set_node_priority(&self, priority: i32) -> commands::SetNodePriority {
+        commands::SetNodePriority::new(
+            self.connection.clone(),
+            priority,
+            self.settings.default_user_name.clone(),
+        )
+    }
 }
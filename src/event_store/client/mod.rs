@@ -1,5 +1,8 @@
 #![allow(clippy::large_enum_variant)]
 pub mod gossip;
+pub mod operations;
 pub mod persistent;
+pub mod projections;
 pub mod shared;
 pub mod streams;
+pub mod users;
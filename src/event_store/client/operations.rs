@@ -0,0 +1,190 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartScavengeReq {
+    #[prost(message, optional, tag = "1")]
+    pub options: ::std::option::Option<start_scavenge_req::Options>,
+}
+pub mod start_scavenge_req {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Options {
+        #[prost(int32, tag = "1")]
+        pub thread_count: i32,
+        #[prost(int32, tag = "2")]
+        pub start_from_chunk: i32,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopScavengeReq {
+    #[prost(message, optional, tag = "1")]
+    pub options: ::std::option::Option<stop_scavenge_req::Options>,
+}
+pub mod stop_scavenge_req {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Options {
+        #[prost(string, tag = "1")]
+        pub scavenge_id: std::string::String,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScavengeResp {
+    #[prost(string, tag = "1")]
+    pub scavenge_id: std::string::String,
+    #[prost(enumeration = "scavenge_resp::ScavengeResult", tag = "2")]
+    pub scavenge_result: i32,
+}
+pub mod scavenge_resp {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum ScavengeResult {
+        Started = 0,
+        InProgress = 1,
+        Stopped = 2,
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetNodePriorityReq {
+    #[prost(int32, tag = "1")]
+    pub priority: i32,
+}
+#[doc = r" Generated client implementations."]
+pub mod operations_client {
+    #![allow(unused_variables, dead_code, missing_docs)]
+    use tonic::codegen::*;
+    pub struct OperationsClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl OperationsClient<tonic::transport::Channel> {
+        #[doc = r" Attempt to create a new client by connecting to a given endpoint."]
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> OperationsClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::ResponseBody: Body + HttpBody + Send + 'static,
+        T::Error: Into<StdError>,
+        <T::ResponseBody as HttpBody>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor(inner: T, interceptor: impl Into<tonic::Interceptor>) -> Self {
+            let inner = tonic::client::Grpc::with_interceptor(inner, interceptor);
+            Self { inner }
+        }
+        pub async fn start_scavenge(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StartScavengeReq>,
+        ) -> Result<tonic::Response<super::ScavengeResp>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/StartScavenge",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn stop_scavenge(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StopScavengeReq>,
+        ) -> Result<tonic::Response<super::ScavengeResp>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/StopScavenge",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn shutdown(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::shared::Empty>,
+        ) -> Result<tonic::Response<super::super::shared::Empty>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/Shutdown",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn merge_indexes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::shared::Empty>,
+        ) -> Result<tonic::Response<super::super::shared::Empty>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/MergeIndexes",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn resign_node(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::shared::Empty>,
+        ) -> Result<tonic::Response<super::super::shared::Empty>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/ResignNode",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn set_node_priority(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetNodePriorityReq>,
+        ) -> Result<tonic::Response<super::super::shared::Empty>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/event_store.client.operations.Operations/SetNodePriority",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+    impl<T: Clone> Clone for OperationsClient<T> {
+        fn clone(&self) -> Self {
+            Self {
+                inner: self.inner.clone(),
+            }
+        }
+    }
+    impl<T> std::fmt::Debug for OperationsClient<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "OperationsClient {{ ... }}")
+        }
+    }
+}
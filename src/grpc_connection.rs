@@ -1,10 +1,10 @@
 use crate::gossip::{Gossip, MemberInfo, VNodeState};
-use crate::types::Endpoint;
+use crate::types::{ConnectionEvent, DisconnectReason, Endpoint, ReconnectionPolicy};
 use crate::{Credentials, DnsClusterSettings, Either, NodePreference};
-use futures::channel::mpsc::UnboundedSender;
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures::channel::oneshot;
 use futures::stream::StreamExt;
-use futures::{Future, SinkExt};
+use futures::{Future, FutureExt, SinkExt};
 use nom::branch::alt;
 use nom::bytes::complete::take_while;
 use nom::combinator::{all_consuming, complete, opt};
@@ -18,7 +18,10 @@ use serde::de::Visitor;
 use serde::{Deserializer, Serializer};
 use std::cmp::Ordering;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tonic::transport::Channel;
 use tonic::Status;
@@ -68,6 +71,44 @@ fn test_connection_string() {
     }
 }
 
+#[test]
+fn test_settings_debug_redacts_client_key() {
+    let settings = "esdb://localhost"
+        .parse::<ConnectionSettings>()
+        .unwrap()
+        .tls_client_identity("CERTIFICATE", "PRIVATE KEY");
+    let debug = format!("{:?}", settings);
+
+    assert!(debug.contains("<redacted>"));
+    assert!(!debug.contains(&format!("{:?}", b"PRIVATE KEY".to_vec())));
+}
+
+#[test]
+fn test_effective_reconnection_policy() {
+    use crate::Retry;
+
+    let single_node = "esdb://localhost".parse::<ConnectionSettings>().unwrap();
+    let cluster = "esdb://localhost:1111,localhost:2222"
+        .parse::<ConnectionSettings>()
+        .unwrap();
+    let policy = ReconnectionPolicy::new(Retry::Only(10));
+
+    assert_eq!(
+        single_node.effective_reconnection_policy().limit,
+        Retry::Undefinately
+    );
+    assert_eq!(
+        cluster.effective_reconnection_policy(),
+        ReconnectionPolicy::default()
+    );
+    assert_eq!(
+        single_node
+            .reconnection_policy(policy)
+            .effective_reconnection_policy(),
+        policy
+    );
+}
+
 #[derive(Clone, Debug)]
 pub struct ConnectionSettingsParseError {
     input: String,
@@ -112,6 +153,26 @@ where
     deserializer.deserialize_any(DurationVisitor)
 }
 
+fn serialize_optional_duration<S>(
+    value: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.serialize_some(&(value.as_millis() as u64)),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_optional_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(DurationVisitor).map(Some)
+}
+
 fn default_max_discover_attempts() -> usize {
     ConnectionSettings::default().max_discover_attempts
 }
@@ -136,10 +197,6 @@ fn default_tls_verify_cert() -> bool {
     ConnectionSettings::default().tls_verify_cert
 }
 
-fn default_throw_on_append_failure() -> bool {
-    ConnectionSettings::default().throw_on_append_failure
-}
-
 /// Gathers all the settings related to a gRPC connection with an EventStoreDB database.
 /// `ConnectionSettings` can only be created when parsing a connection string.
 ///
@@ -175,20 +232,31 @@ fn default_throw_on_append_failure() -> bool {
 /// `ConnectionSettings` supports a wide range of settings. If a setting is not mentioned in the
 /// connection string, that setting default value is used.
 ///
-/// * `maxDiscoverAttempts`: default `3`. Maximum number of DNS discovery attempts before the
-///    connection gives up.
-///    __*TODO - Current behavior keeps retrying endlessly.*__
+/// * `maxDiscoverAttempts`: default `3`. Maximum number of attempts to reach a node before a
+///   cluster connection gives up and fails the pending requests.
 ///
-/// * `discoveryInterval`: default `500ms`. Waiting period between discovery attempts.
+/// * `discoveryInterval`: default `500ms`. Waiting period after the first failed attempt. It
+///   doubles after each attempt, up to 10s.
 ///
 /// * `gossipTimeout`: default `3s`: Waiting period before a gossip request timeout.
-///    __*TODO - Current behavior doesn't timeout at all.*__
+///
+/// * `defaultDeadline`: default none. Maximum amount of time, in milliseconds, a command waits
+///   for the server to answer before failing with `Error::DeadlineExceeded`. Commands can
+///   override it with their `deadline` method. Reads and list commands apply it to every item
+///   they stream. Subscriptions only apply it to their confirmation, they can stay idle for as
+///   long as no event shows up.
 ///
 /// * `tls`: default `true`. Use a secure connection.
 ///
 /// * `tlsVerifyCert`: default `true`. When using a secure connection, perform a certification
 ///    verification.
 ///
+/// * `tlsCaFile`: default none. Path to a PEM file holding the certificate authority used to
+///   verify the server certificate, like the one generated by `es-gencert-cli`.
+///
+/// * `userCertFile` and `userKeyFile`: default none. Paths to a PEM client certificate and its
+///   private key, presented to the server when it requires mutual TLS. Both must be set.
+///
 /// * `nodePreference`: default `random`. When in a cluster connection, indicates what type of node
 ///    a connection should pick. Keep in mind that's best effort. Supported values are:
 ///    * `leader`
@@ -196,13 +264,19 @@ fn default_throw_on_append_failure() -> bool {
 ///    * `follower`
 ///    * `readOnlyReplica`
 ///
-/// * `throwOnAppendFailure`: default `true`. If the client raise an exception when facing a `WrongExpectedVersion`.
-///    __*TODO - Not supported yet.*__
+/// * `throwOnAppendFailure`: deprecated, has no effect. Writes always fail with
+///   `Error::WrongExpectedVersion` on an expected version mismatch, the `*_checked` write methods
+///   return it as a value instead. The option is still parsed so existing connection strings keep
+///   working.
 ///
 /// * `dnsLookUpType`: default `a`. DNS record type we are looking for during discovery of cluster
 ///   nodes. Default behaviour is looking for A records. Supported values are:
 ///   * `a`
 ///   * `srv`
+///
+/// Use `ConnectionSettings::reconnection_policy` for finer control over how the connection
+/// retries to reach a node. Without it, a single-node connection retries indefinitely and only
+/// cluster connections give up after `maxDiscoverAttempts`.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConnectionSettings {
     #[serde(default)]
@@ -223,14 +297,31 @@ pub struct ConnectionSettings {
         deserialize_with = "deserialize_duration"
     )]
     pub(crate) gossip_timeout: Duration,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_duration",
+        deserialize_with = "deserialize_optional_duration"
+    )]
+    pub(crate) default_deadline: Option<Duration>,
     #[serde(default = "default_preference")]
     pub(crate) preference: NodePreference,
     #[serde(default = "default_secure")]
     pub(crate) secure: bool,
     #[serde(default = "default_tls_verify_cert")]
     pub(crate) tls_verify_cert: bool,
-    #[serde(default = "default_throw_on_append_failure")]
-    pub(crate) throw_on_append_failure: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tls_ca_file: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) user_cert_file: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) user_key_file: Option<PathBuf>,
+    #[serde(skip)]
+    pub(crate) tls_ca_certificate: Option<Vec<u8>>,
+    #[serde(skip)]
+    pub(crate) tls_client_identity: Option<PemIdentity>,
+    #[serde(skip)]
+    pub(crate) reconnection: Option<ReconnectionPolicy>,
     #[serde(default)]
     pub(crate) default_user_name: Option<Credentials>,
     #[serde(default)]
@@ -354,6 +445,15 @@ impl ConnectionSettings {
                             }
                         }
 
+                        "defaultDeadline" => {
+                            let value = values.as_slice()[1];
+                            if let Ok(millis) = value.parse() {
+                                result.default_deadline = Some(Duration::from_millis(millis));
+                            } else {
+                                return Err(nom::Err::Failure((value, ErrorKind::ParseTo)));
+                            }
+                        }
+
                         "tls" => {
                             let value = values.as_slice()[1];
                             if let Ok(bool) = value.parse() {
@@ -372,6 +472,18 @@ impl ConnectionSettings {
                             }
                         }
 
+                        "tlsCaFile" => {
+                            result.tls_ca_file = Some(PathBuf::from(values.as_slice()[1]));
+                        }
+
+                        "userCertFile" => {
+                            result.user_cert_file = Some(PathBuf::from(values.as_slice()[1]));
+                        }
+
+                        "userKeyFile" => {
+                            result.user_key_file = Some(PathBuf::from(values.as_slice()[1]));
+                        }
+
                         "nodePreference" => match values.as_slice()[1] {
                             "follower" => {
                                 result.preference = NodePreference::Follower;
@@ -396,8 +508,8 @@ impl ConnectionSettings {
 
                         "throwOnAppendFailure" => {
                             let value = values.as_slice()[1];
-                            if let Ok(bool) = value.parse() {
-                                result.throw_on_append_failure = bool;
+                            if value.parse::<bool>().is_ok() {
+                                warn!("throwOnAppendFailure is deprecated and has no effect");
                             } else {
                                 return Err(nom::Err::Failure((value, ErrorKind::ParseTo)));
                             }
@@ -455,6 +567,78 @@ impl ConnectionSettings {
         }
     }
 
+    /// Trusts the certificate authority of the given PEM content when verifying the server
+    /// certificate. Takes precedence over `tlsCaFile`.
+    ///
+    /// ```no_run
+    /// # use eventstore::ConnectionSettings;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ca = std::fs::read("certs/ca/ca.crt")?;
+    /// let setts = "esdb://localhost:2113"
+    ///     .parse::<ConnectionSettings>()?
+    ///     .tls_ca_certificate(ca);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tls_ca_certificate(self, pem: impl Into<Vec<u8>>) -> Self {
+        ConnectionSettings {
+            tls_ca_certificate: Some(pem.into()),
+            ..self
+        }
+    }
+
+    /// Presents the given PEM client certificate and private key to the server, for
+    /// deployments requiring mutual TLS. Takes precedence over `userCertFile` and
+    /// `userKeyFile`.
+    pub fn tls_client_identity(
+        self,
+        certificate_pem: impl Into<Vec<u8>>,
+        key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        ConnectionSettings {
+            tls_client_identity: Some((certificate_pem.into(), PemKey(key_pem.into()))),
+            ..self
+        }
+    }
+
+    /// Sets how the connection retries to reach a node. Overrides `maxDiscoverAttempts` and
+    /// `discoveryInterval`.
+    ///
+    /// ```
+    /// # use eventstore::{ConnectionSettings, ReconnectionPolicy, Retry};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let policy = ReconnectionPolicy::new(Retry::Only(10)).max_delay(Duration::from_secs(30));
+    /// let setts = "esdb://localhost:2113"
+    ///     .parse::<ConnectionSettings>()?
+    ///     .reconnection_policy(policy);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reconnection_policy(self, policy: ReconnectionPolicy) -> Self {
+        ConnectionSettings {
+            reconnection: Some(policy),
+            ..self
+        }
+    }
+
+    pub(crate) fn effective_reconnection_policy(&self) -> ReconnectionPolicy {
+        self.reconnection.unwrap_or_else(|| {
+            let limit = if self.is_single_node() {
+                crate::Retry::Undefinately
+            } else {
+                // `maxDiscoverAttempts` counts the first attempt.
+                crate::Retry::Only(self.max_discover_attempts.saturating_sub(1))
+            };
+
+            ReconnectionPolicy::new(limit).delay(self.discovery_interval)
+        })
+    }
+
+    fn is_single_node(&self) -> bool {
+        !self.dns_discover && self.hosts.len() <= 1
+    }
+
     pub fn to_uri(&self, endpoint: &Endpoint) -> http::Uri {
         let scheme = if self.secure { "https" } else { "http" };
 
@@ -480,10 +664,16 @@ impl Default for ConnectionSettings {
             max_discover_attempts: 3,
             discovery_interval: Duration::from_millis(500),
             gossip_timeout: Duration::from_secs(3),
+            default_deadline: None,
             preference: Default::default(),
             secure: true,
             tls_verify_cert: true,
-            throw_on_append_failure: true,
+            tls_ca_file: None,
+            user_cert_file: None,
+            user_key_file: None,
+            tls_ca_certificate: None,
+            tls_client_identity: None,
+            reconnection: None,
             default_user_name: None,
             dns_lookup_type: crate::LookupType::LookupA,
         }
@@ -492,6 +682,9 @@ impl Default for ConnectionSettings {
 
 async fn cluster_mode_connection(
     conn_setts: ConnectionSettings,
+    listeners: StateListeners,
+    closing: Signal,
+    exited: oneshot::Sender<()>,
 ) -> Result<UnboundedSender<Msg>, Box<dyn std::error::Error>> {
     let (sender, mut consumer) = futures::channel::mpsc::unbounded::<Msg>();
     let kind = if conn_setts.dns_discover {
@@ -514,17 +707,27 @@ async fn cluster_mode_connection(
     };
 
     tokio::spawn(async move {
+        // Dropped when the task exits, which `close` waits for.
+        let _exited = exited;
         let mut channel: Option<Channel> = None;
         let mut channel_id = Uuid::new_v4();
         let mut failed_endpoint: Option<Endpoint> = None;
         let mut previous_candidates: Option<Vec<Member>> = None;
         let mut work_queue = Vec::new();
         let mut rng = SmallRng::from_entropy();
+        let mut attempts = 0;
+        let mut connected = false;
+        let policy = conn_setts.effective_reconnection_policy();
 
-        while let Some(item) = consumer.next().await {
+        while let Some(item) = next_msg(&mut consumer, &closing).await {
             work_queue.push(item);
 
             while let Some(msg) = work_queue.pop() {
+                if closing.is_fired() {
+                    listeners.close();
+                    return;
+                }
+
                 debug!("Current msg: {:?}, rest: [{:?}]", msg, work_queue);
                 match msg {
                     Msg::GetChannel(resp) => {
@@ -547,8 +750,19 @@ async fn cluster_mode_connection(
                             continue;
                         }
 
+                        if connected {
+                            connected = false;
+                            listeners.emit(ConnectionEvent::Disconnected(
+                                DisconnectReason::from_seed(&seed_opt),
+                            ));
+                        }
+
+                        // A seed is the leader endpoint sent along a not-leader error.
                         let node = if let Some(ref seed) = seed_opt {
-                            Some(seed.clone())
+                            Some(Member {
+                                endpoint: seed.clone(),
+                                state: VNodeState::Leader,
+                            })
                         } else {
                             node_selection(
                                 &conn_setts,
@@ -560,12 +774,34 @@ async fn cluster_mode_connection(
                             .await
                         };
 
-                        if let Some(node) = node {
+                        if let Some(Member {
+                            endpoint: node,
+                            state,
+                        }) = node
+                        {
+                            listeners.emit(ConnectionEvent::Connecting(node.clone()));
+
                             match create_channel(&conn_setts, &node).await {
                                 Ok(new_channel) => {
+                                    if let Some(previous) = failed_endpoint.take() {
+                                        if previous != node {
+                                            listeners.emit(ConnectionEvent::NodeReselected {
+                                                previous,
+                                                current: node.clone(),
+                                            });
+                                        }
+                                    }
+
+                                    listeners.emit(ConnectionEvent::Connected(
+                                        node.clone(),
+                                        Some(state),
+                                    ));
+
                                     failed_endpoint = Some(node);
                                     channel_id = Uuid::new_v4();
                                     channel = Some(new_channel);
+                                    attempts = 0;
+                                    connected = true;
 
                                     continue;
                                 }
@@ -581,29 +817,100 @@ async fn cluster_mode_connection(
                             warn!("Unable to select a node. Retrying...");
                         }
 
-                        tokio::time::delay_for(conn_setts.discovery_interval).await;
+                        attempts += 1;
+
+                        if policy.exhausted(attempts) {
+                            fail_pending_requests(
+                                &mut consumer,
+                                &mut work_queue,
+                                &listeners,
+                                attempts,
+                            );
+                            attempts = 0;
+                            continue;
+                        }
+
+                        pause(policy.backoff(attempts, &mut rng), &closing).await;
                         work_queue.push(Msg::CreateChannel(id, seed_opt));
                     }
                 }
             }
         }
+
+        listeners.close();
     });
 
     Ok(sender)
 }
 
-fn single_node_mode(conn_setts: ConnectionSettings, endpoint: Endpoint) -> UnboundedSender<Msg> {
+// Returns the next message, or `None` once the connection is closed.
+async fn next_msg(consumer: &mut UnboundedReceiver<Msg>, closing: &Signal) -> Option<Msg> {
+    match futures::future::select(consumer.next(), closing.wait()).await {
+        futures::future::Either::Left((msg, _)) => msg,
+        futures::future::Either::Right(_) => None,
+    }
+}
+
+// Waits for the given delay, or less if the connection gets closed.
+async fn pause(delay: Duration, closing: &Signal) {
+    let _ = futures::future::select(tokio::time::delay_for(delay), closing.wait()).await;
+}
+
+// Notifies every caller waiting for a channel that no node could be reached.
+// The next request triggers a new connection attempt.
+fn fail_pending_requests(
+    consumer: &mut UnboundedReceiver<Msg>,
+    work_queue: &mut Vec<Msg>,
+    listeners: &StateListeners,
+    attempts: usize,
+) {
+    error!(
+        "Failed to reach a node after {} attempts. Giving up",
+        attempts
+    );
+    listeners.emit(ConnectionEvent::DiscoveryFailed(attempts));
+
+    while let Some(Some(msg)) = consumer.next().now_or_never() {
+        work_queue.push(msg);
+    }
+
+    for msg in work_queue.drain(..) {
+        if let Msg::GetChannel(resp) = msg {
+            let _ = resp.send(Err(crate::Error::DiscoveryFailed(attempts)));
+        }
+    }
+}
+
+fn single_node_mode(
+    conn_setts: ConnectionSettings,
+    endpoint: Endpoint,
+    listeners: StateListeners,
+    closing: Signal,
+    exited: oneshot::Sender<()>,
+) -> UnboundedSender<Msg> {
     let (sender, mut consumer) = futures::channel::mpsc::unbounded::<Msg>();
 
     tokio::spawn(async move {
+        // Dropped when the task exits, which `close` waits for.
+        let _exited = exited;
         let mut channel: Option<Channel> = None;
         let mut channel_id = Uuid::new_v4();
         let mut work_queue = Vec::new();
+        let mut rng = SmallRng::from_entropy();
+        let mut attempts = 0;
+        let mut current: Option<Endpoint> = None;
+        let mut connected = false;
+        let policy = conn_setts.effective_reconnection_policy();
 
-        while let Some(item) = consumer.next().await {
+        while let Some(item) = next_msg(&mut consumer, &closing).await {
             work_queue.push(item);
 
             while let Some(msg) = work_queue.pop() {
+                if closing.is_fired() {
+                    listeners.close();
+                    return;
+                }
+
                 match msg {
                     Msg::GetChannel(resp) => {
                         if let Some(channel) = channel.as_ref() {
@@ -625,16 +932,39 @@ fn single_node_mode(conn_setts: ConnectionSettings, endpoint: Endpoint) -> Unbou
                             continue;
                         }
 
+                        if connected {
+                            connected = false;
+                            listeners.emit(ConnectionEvent::Disconnected(
+                                DisconnectReason::from_seed(&seed_opt),
+                            ));
+                        }
+
                         let node = if let Some(ref seed) = seed_opt {
                             seed.clone()
                         } else {
                             endpoint.clone()
                         };
 
+                        listeners.emit(ConnectionEvent::Connecting(node.clone()));
+
                         match create_channel(&conn_setts, &node).await {
                             Ok(new_channel) => {
+                                if let Some(previous) = current.take() {
+                                    if previous != node {
+                                        listeners.emit(ConnectionEvent::NodeReselected {
+                                            previous,
+                                            current: node.clone(),
+                                        });
+                                    }
+                                }
+
+                                listeners.emit(ConnectionEvent::Connected(node.clone(), None));
+
                                 channel_id = Uuid::new_v4();
                                 channel = Some(new_channel);
+                                attempts = 0;
+                                current = Some(node);
+                                connected = true;
                             }
 
                             Err(err) => {
@@ -644,7 +974,20 @@ fn single_node_mode(conn_setts: ConnectionSettings, endpoint: Endpoint) -> Unbou
                                     err
                                 );
 
-                                tokio::time::delay_for(conn_setts.discovery_interval).await;
+                                attempts += 1;
+
+                                if policy.exhausted(attempts) {
+                                    fail_pending_requests(
+                                        &mut consumer,
+                                        &mut work_queue,
+                                        &listeners,
+                                        attempts,
+                                    );
+                                    attempts = 0;
+                                    continue;
+                                }
+
+                                pause(policy.backoff(attempts, &mut rng), &closing).await;
                                 work_queue.push(Msg::CreateChannel(id, seed_opt));
                             }
                         }
@@ -652,20 +995,215 @@ fn single_node_mode(conn_setts: ConnectionSettings, endpoint: Endpoint) -> Unbou
                 }
             }
         }
+
+        listeners.close();
     });
 
     sender
 }
 
-async fn create_channel(
+/// Every receiver of connection state changes, the closed ones are removed
+/// on the next event.
+#[derive(Clone, Default)]
+struct StateListeners(Arc<Mutex<Vec<UnboundedSender<ConnectionEvent>>>>);
+
+impl StateListeners {
+    fn subscribe(&self) -> UnboundedReceiver<ConnectionEvent> {
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+
+        if let Ok(mut listeners) = self.0.lock() {
+            listeners.push(sender);
+        }
+
+        receiver
+    }
+
+    fn emit(&self, event: ConnectionEvent) {
+        debug!("Connection state changed: {:?}", event);
+
+        if let Ok(mut listeners) = self.0.lock() {
+            listeners.retain(|listener| listener.unbounded_send(event.clone()).is_ok());
+        }
+    }
+
+    // Notifies that the connection is closed and ends every stream.
+    fn close(&self) {
+        self.emit(ConnectionEvent::Disconnected(DisconnectReason::Closed));
+
+        if let Ok(mut listeners) = self.0.lock() {
+            listeners.clear();
+        }
+    }
+}
+
+/// Notification shared by every clone of a connection, that fires only once.
+#[derive(Clone)]
+struct Signal {
+    fired: Arc<AtomicBool>,
+    trigger: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+    receiver: futures::future::Shared<oneshot::Receiver<()>>,
+}
+
+impl Signal {
+    fn new() -> Self {
+        let (trigger, receiver) = oneshot::channel();
+
+        Signal {
+            fired: Arc::new(AtomicBool::new(false)),
+            trigger: Arc::new(Mutex::new(Some(trigger))),
+            receiver: receiver.shared(),
+        }
+    }
+
+    fn fire(&self) {
+        self.fired.store(true, AtomicOrdering::SeqCst);
+
+        // Dropping the sender completes every receiver.
+        if let Ok(mut trigger) = self.trigger.lock() {
+            trigger.take();
+        }
+    }
+
+    fn is_fired(&self) -> bool {
+        self.fired.load(AtomicOrdering::SeqCst)
+    }
+
+    fn wait(&self) -> impl Future<Output = ()> + Send + Unpin {
+        self.receiver.clone().map(|_| ())
+    }
+}
+
+/// Tracks the closing of a connection, shared by all its clones.
+#[derive(Clone)]
+struct Lifecycle {
+    // Rejects new actions and ends subscriptions.
+    closing: Signal,
+    // Aborts the actions still running once the grace period is over.
+    aborting: Signal,
+    in_flight: Arc<InFlightCount>,
+    exited: futures::future::Shared<oneshot::Receiver<()>>,
+}
+
+/// Number of actions running, notifies when it drops to zero.
+struct InFlightCount {
+    count: AtomicUsize,
+    drained: tokio::sync::Notify,
+}
+
+impl InFlightCount {
+    fn new() -> Self {
+        InFlightCount {
+            count: AtomicUsize::new(0),
+            drained: tokio::sync::Notify::new(),
+        }
+    }
+
+    async fn drained(&self) {
+        // A notification sent between the check and the wait is kept by
+        // `Notify`, so it isn't missed.
+        while self.count.load(AtomicOrdering::SeqCst) > 0 {
+            self.drained.notified().await;
+        }
+    }
+}
+
+struct InFlight(Arc<InFlightCount>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, AtomicOrdering::SeqCst) == 1 {
+            self.0.drained.notify();
+        }
+    }
+}
+
+impl Lifecycle {
+    fn track(&self) -> InFlight {
+        self.in_flight.count.fetch_add(1, AtomicOrdering::SeqCst);
+
+        InFlight(self.in_flight.clone())
+    }
+}
+
+impl DisconnectReason {
+    fn from_seed(seed: &Option<Endpoint>) -> Self {
+        match seed {
+            Some(leader) => DisconnectReason::NotLeader(leader.clone()),
+            None => DisconnectReason::NodeUnavailable,
+        }
+    }
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+// PEM certificate chain and private key.
+type PemIdentity = (Vec<u8>, PemKey);
+
+// PEM private key, redacted when debugging the settings so logging them
+// doesn't leak it.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct PemKey(Vec<u8>);
+
+impl std::fmt::Debug for PemKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+fn invalid_pem(what: &str) -> BoxError {
+    format!("Invalid PEM content for the {}", what).into()
+}
+
+// Reads the CA and client identity PEM content, programmatic values taking
+// precedence over files. Files are read every time a channel is created so
+// rotated certificates get picked up.
+fn load_tls_pems(
     setts: &ConnectionSettings,
-    endpoint: &Endpoint,
-) -> Result<Channel, tonic::transport::Error> {
-    let uri = setts.to_uri(endpoint);
+) -> Result<(Option<Vec<u8>>, Option<PemIdentity>), BoxError> {
+    let ca = match (&setts.tls_ca_certificate, &setts.tls_ca_file) {
+        (Some(pem), _) => Some(pem.clone()),
+        (None, Some(path)) => Some(
+            std::fs::read(path)
+                .map_err(|e| format!("Failed to read TLS CA file {}: {}", path.display(), e))?,
+        ),
+        (None, None) => None,
+    };
 
-    debug!("Create gRPC channel for: {}", uri);
+    let identity = match (
+        &setts.tls_client_identity,
+        &setts.user_cert_file,
+        &setts.user_key_file,
+    ) {
+        (Some(identity), _, _) => Some(identity.clone()),
+        (None, Some(cert), Some(key)) => {
+            let read = |path: &PathBuf| {
+                std::fs::read(path).map_err(|e| {
+                    format!("Failed to read TLS client file {}: {}", path.display(), e)
+                })
+            };
 
-    let mut channel = Channel::builder(uri.clone());
+            Some((read(cert)?, PemKey(read(key)?)))
+        }
+        (None, None, None) => None,
+        _ => return Err("Both userCertFile and userKeyFile must be set".into()),
+    };
+
+    Ok((ca, identity))
+}
+
+fn tls_config(
+    setts: &ConnectionSettings,
+) -> Result<Option<tonic::transport::ClientTlsConfig>, BoxError> {
+    use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+
+    let (ca, identity) = load_tls_pems(setts)?;
+
+    if let Some(ca) = ca.as_ref() {
+        match rustls::internal::pemfile::certs(&mut ca.as_slice()) {
+            Ok(certs) if !certs.is_empty() => {}
+            _ => return Err(invalid_pem("CA certificate")),
+        }
+    }
 
     if !setts.tls_verify_cert {
         let mut rustls_config = rustls::ClientConfig::new();
@@ -677,9 +1215,67 @@ async fn create_channel(
             .dangerous()
             .set_certificate_verifier(std::sync::Arc::new(NoVerification));
 
-        let client_config =
-            tonic::transport::ClientTlsConfig::new().rustls_client_config(rustls_config);
+        if let Some((cert, key)) = identity {
+            use rustls::internal::pemfile;
+
+            let certs = pemfile::certs(&mut cert.as_slice())
+                .map_err(|_| invalid_pem("client certificate"))?;
+            let mut keys = pemfile::pkcs8_private_keys(&mut key.0.as_slice())
+                .map_err(|_| invalid_pem("client key"))?;
+
+            if keys.is_empty() {
+                keys = pemfile::rsa_private_keys(&mut key.0.as_slice())
+                    .map_err(|_| invalid_pem("client key"))?;
+            }
+
+            let key = keys.pop().ok_or_else(|| invalid_pem("client key"))?;
+
+            rustls_config.set_single_client_cert(certs, key)?;
+        }
+
+        return Ok(Some(
+            ClientTlsConfig::new().rustls_client_config(rustls_config),
+        ));
+    }
+
+    if ca.is_none() && identity.is_none() {
+        return Ok(None);
+    }
+
+    let mut config = ClientTlsConfig::new();
+
+    if let Some(ca) = ca {
+        config = config.ca_certificate(Certificate::from_pem(ca));
+    }
+
+    if let Some((cert, key)) = identity {
+        config = config.identity(Identity::from_pem(cert, key.0));
+    }
+
+    Ok(Some(config))
+}
 
+fn check_tls_config(setts: &ConnectionSettings) -> Result<(), BoxError> {
+    if let (true, Some(endpoint)) = (setts.secure, setts.hosts.first()) {
+        if let Some(client_config) = tls_config(setts)? {
+            Channel::builder(setts.to_uri(endpoint)).tls_config(client_config)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_channel(
+    setts: &ConnectionSettings,
+    endpoint: &Endpoint,
+) -> Result<Channel, BoxError> {
+    let uri = setts.to_uri(endpoint);
+
+    debug!("Create gRPC channel for: {}", uri);
+
+    let mut channel = Channel::builder(uri.clone());
+
+    if let Some(client_config) = tls_config(setts)? {
         channel = channel.tls_config(client_config)?;
     }
 
@@ -696,7 +1292,7 @@ struct Handle {
 }
 
 enum Msg {
-    GetChannel(oneshot::Sender<Result<Handle, Status>>),
+    GetChannel(oneshot::Sender<crate::Result<Handle>>),
     CreateChannel(Uuid, Option<Endpoint>),
 }
 
@@ -713,15 +1309,34 @@ impl std::fmt::Debug for Msg {
 
 #[derive(Clone)]
 pub struct GrpcConnection {
+    settings: ConnectionSettings,
     sender: futures::channel::mpsc::UnboundedSender<Msg>,
+    deadline: Option<Duration>,
+    listeners: StateListeners,
+    lifecycle: Lifecycle,
 }
 
 impl GrpcConnection {
     pub async fn create(
         conn_setts: ConnectionSettings,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let sender = if conn_setts.dns_discover || conn_setts.hosts.len() > 1 {
-            cluster_mode_connection(conn_setts).await?
+        let settings = conn_setts.clone();
+
+        // Fails early on unreadable or malformed certificates instead of
+        // retrying to connect forever.
+        check_tls_config(&settings).map_err(|e| e as Box<dyn std::error::Error>)?;
+
+        let listeners = StateListeners::default();
+        let closing = Signal::new();
+        let (exited_sender, exited) = oneshot::channel();
+        let sender = if !conn_setts.is_single_node() {
+            cluster_mode_connection(
+                conn_setts,
+                listeners.clone(),
+                closing.clone(),
+                exited_sender,
+            )
+            .await?
         } else {
             let endpoint = conn_setts
                 .hosts
@@ -729,10 +1344,128 @@ impl GrpcConnection {
                 .expect("Impossible: hosts can't be empty")
                 .clone();
 
-            single_node_mode(conn_setts, endpoint)
+            single_node_mode(
+                conn_setts,
+                endpoint,
+                listeners.clone(),
+                closing.clone(),
+                exited_sender,
+            )
+        };
+
+        let deadline = settings.default_deadline;
+        let lifecycle = Lifecycle {
+            closing,
+            aborting: Signal::new(),
+            in_flight: Arc::new(InFlightCount::new()),
+            exited: exited.shared(),
         };
 
-        Ok(GrpcConnection { sender })
+        Ok(GrpcConnection {
+            settings,
+            sender,
+            deadline,
+            listeners,
+            lifecycle,
+        })
+    }
+
+    /// Closes the connection. New actions fail with
+    /// `Error::ConnectionClosed` right away, subscriptions end and the
+    /// actions already running get the grace period to complete before
+    /// being aborted. Completes once the background task has exited.
+    pub async fn close(&self, grace_period: Duration) {
+        self.lifecycle.closing.fire();
+
+        let drained = self.lifecycle.in_flight.drained();
+
+        if tokio::time::timeout(grace_period, drained).await.is_err() {
+            warn!(
+                "Aborting the operations still running after {:?}",
+                grace_period
+            );
+        }
+
+        self.lifecycle.aborting.fire();
+
+        let _ = self.lifecycle.exited.clone().await;
+    }
+
+    /// Completes when the connection starts closing.
+    pub(crate) fn closed(&self) -> impl Future<Output = ()> + Send + Unpin {
+        self.lifecycle.closing.wait()
+    }
+
+    /// Tells if the connection is closing or closed.
+    pub(crate) fn is_closing(&self) -> bool {
+        self.lifecycle.closing.is_fired()
+    }
+
+    /// Returns a stream of the changes of the connection state, starting
+    /// from now.
+    pub fn state_changes(&self) -> UnboundedReceiver<ConnectionEvent> {
+        self.listeners.subscribe()
+    }
+
+    /// Overrides the connection default deadline for the actions executed
+    /// through this handle.
+    pub(crate) fn set_deadline(&mut self, deadline: Duration) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Deadline of the actions executed through this handle, if any.
+    pub(crate) fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    // Runs an action, tracked as in-flight so `close` can wait for it.
+    // tonic doesn't forward the `grpc-timeout` header, so the deadline is
+    // enforced on our side. Dropping the call resets the HTTP/2 stream.
+    async fn run<Fut, A>(&self, action: Fut) -> crate::Result<A>
+    where
+        Fut: Future<Output = Result<A, Status>>,
+    {
+        let _in_flight = self.lifecycle.track();
+        let deadline = self.deadline;
+        let action = async move {
+            match deadline {
+                None => action.await.map_err(crate::Error::from_grpc),
+                Some(deadline) => match tokio::time::timeout(deadline, action).await {
+                    Ok(result) => result.map_err(crate::Error::from_grpc),
+                    Err(_) => Err(crate::Error::DeadlineExceeded),
+                },
+            }
+        };
+
+        futures::pin_mut!(action);
+
+        match futures::future::select(action, self.lifecycle.aborting.wait()).await {
+            futures::future::Either::Left((result, _)) => result,
+            futures::future::Either::Right(_) => Err(crate::Error::ConnectionClosed),
+        }
+    }
+
+    /// Executes an action on a specific node, bypassing node selection. The
+    /// gRPC channel is only used for that action.
+    pub async fn execute_on<F, Fut, A>(&self, endpoint: &Endpoint, action: F) -> crate::Result<A>
+    where
+        F: FnOnce(Channel) -> Fut + Send,
+        Fut: Future<Output = Result<A, Status>> + Send,
+        A: Send,
+    {
+        if self.lifecycle.closing.is_fired() {
+            return Err(crate::Error::ConnectionClosed);
+        }
+
+        let channel = create_channel(&self.settings, endpoint)
+            .await
+            .map_err(|e| {
+                error!("Error when connecting to {:?}: {}", endpoint, e);
+
+                crate::Error::ConnectionFailed(endpoint.clone(), e)
+            })?;
+
+        self.run(action(channel)).await
     }
 
     pub async fn execute<F, Fut, A>(&self, action: F) -> crate::Result<A>
@@ -741,56 +1474,74 @@ impl GrpcConnection {
         Fut: Future<Output = Result<A, Status>> + Send,
         A: Send,
     {
+        self.execute_tracked(action).await.map(|(_, a)| a)
+    }
+
+    /// Like `execute` but also returns the id of the channel the action ran
+    /// on. Long-running operations like subscriptions can fail after the
+    /// action completed, that id is used to report it through
+    /// `channel_failed`.
+    pub async fn execute_tracked<F, Fut, A>(&self, action: F) -> crate::Result<(Uuid, A)>
+    where
+        F: FnOnce(Channel) -> Fut + Send,
+        Fut: Future<Output = Result<A, Status>> + Send,
+        A: Send,
+    {
+        if self.lifecycle.closing.is_fired() {
+            return Err(crate::Error::ConnectionClosed);
+        }
+
         let (sender, consumer) = futures::channel::oneshot::channel();
 
         let _ = self.sender.clone().send(Msg::GetChannel(sender)).await;
 
         let handle = match consumer.await {
-            Ok(handle) => handle.map_err(crate::Error::from_grpc),
+            Ok(handle) => handle,
             Err(_) => Err(crate::Error::ConnectionClosed),
         }?;
 
-        match action(handle.channel).await {
-            Err(status) => {
-                let err = crate::Error::from_grpc(status);
-
-                match &err {
-                    crate::Error::ServerError => {
-                        error!(
-                            "Current selected EventStoreDB node gone unavailable. Starting node selection process"
-                        );
+        match self.run(action(handle.channel)).await {
+            Err(err) => {
+                self.channel_failed(handle.id, &err).await;
 
-                        let _ = self
-                            .sender
-                            .clone()
-                            .send(Msg::CreateChannel(handle.id, None))
-                            .await;
-                    }
-
-                    crate::Error::NotLeaderException(leader) => {
-                        let _ = self
-                            .sender
-                            .clone()
-                            .send(Msg::CreateChannel(handle.id, Some(leader.clone())))
-                            .await;
+                Err(err)
+            }
 
-                        warn!(
-                            "NotLeaderException found. Start reconnection process on: {:?}",
-                            leader
-                        );
-                    }
+            Ok(a) => Ok((handle.id, a)),
+        }
+    }
 
-                    crate::Error::Grpc(status) => {
-                        debug!("Map: {:?}", status.metadata());
-                    }
+    /// Reports an error that happened on the channel with the given id. If
+    /// the error means the node is gone or isn't the leader anymore, a new
+    /// channel is created.
+    pub async fn channel_failed(&self, id: Uuid, err: &crate::Error) {
+        match err {
+            crate::Error::ServerError => {
+                error!(
+                    "Current selected EventStoreDB node gone unavailable. Starting node selection process"
+                );
+
+                let _ = self.sender.clone().send(Msg::CreateChannel(id, None)).await;
+            }
 
-                    _ => unreachable!(),
-                }
+            crate::Error::NotLeaderException(leader) => {
+                let _ = self
+                    .sender
+                    .clone()
+                    .send(Msg::CreateChannel(id, Some(leader.clone())))
+                    .await;
+
+                warn!(
+                    "NotLeaderException found. Start reconnection process on: {:?}",
+                    leader
+                );
+            }
 
-                Err(err)
+            crate::Error::Grpc(status) => {
+                debug!("Map: {:?}", status.metadata());
             }
 
-            Ok(a) => Ok(a),
+            _ => {}
         }
     }
 }
@@ -806,7 +1557,7 @@ async fn node_selection(
     failed_endpoint: &Option<Endpoint>,
     rng: &mut SmallRng,
     previous_candidates: &mut Option<Vec<Member>>,
-) -> Option<Endpoint> {
+) -> Option<Member> {
     let candidates = match previous_candidates.take() {
         Some(old_candidates) => {
             let mut new_candidates = candidates_from_old_gossip(&failed_endpoint, old_candidates);
@@ -841,13 +1592,19 @@ async fn node_selection(
     debug!("List of candidates: {:?}", candidates);
 
     for candidate in candidates {
-        match create_channel(conn_setts, &candidate).await {
-            Ok(channel) => {
+        let channel = tokio::time::timeout(
+            conn_setts.gossip_timeout,
+            create_channel(conn_setts, &candidate),
+        )
+        .await;
+
+        match channel {
+            Ok(Ok(channel)) => {
                 let gossip_client = Gossip::create(channel.clone());
 
                 debug!("Calling gossip endpoint on: {:?}", candidate);
-                match gossip_client.read().await {
-                    Ok(members_info) => {
+                match tokio::time::timeout(conn_setts.gossip_timeout, gossip_client.read()).await {
+                    Ok(Ok(members_info)) => {
                         debug!("Candidate {:?} gossip info: {:?}", candidate, members_info);
                         let selected_node = determine_best_node(
                             rng,
@@ -859,19 +1616,30 @@ async fn node_selection(
                             return Some(selected_node);
                         }
                     }
-                    Err(err) => {
+                    Ok(Err(err)) => {
                         debug!(
                             "Failed to retrieve gossip information from candidate {:?}: {}",
                             &candidate, err
                         );
                     }
+                    Err(_) => {
+                        debug!(
+                            "Gossip request to candidate {:?} timed out after {:?}",
+                            &candidate, conn_setts.gossip_timeout
+                        );
+                    }
                 }
             }
 
-            Err(err) => debug!(
+            Ok(Err(err)) => debug!(
                 "Failed to create gRPC channel for candidate {:?}: {}",
                 candidate, err
             ),
+
+            Err(_) => debug!(
+                "Creating a gRPC channel for candidate {:?} timed out after {:?}",
+                candidate, conn_setts.gossip_timeout
+            ),
         }
     }
 
@@ -982,7 +1750,7 @@ fn determine_best_node(
     rng: &mut SmallRng,
     preference: NodePreference,
     members: &[MemberInfo],
-) -> Option<Endpoint> {
+) -> Option<Member> {
     fn allowed_states(state: VNodeState) -> bool {
         !matches!(
             state,
@@ -1047,6 +1815,9 @@ fn determine_best_node(
             member.http_end_point.host, member.http_end_point.port, member.state
         );
 
-        member.http_end_point.clone()
+        Member {
+            endpoint: member.http_end_point.clone(),
+            state: member.state,
+        }
     })
 }
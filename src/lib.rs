@@ -77,6 +77,10 @@ mod gossip;
 mod grpc_connection;
 mod types;
 
-pub use connection::EventStoreDBConnection;
+pub use commands::{FilterConf, PersistentSubscription, SubscriptionRead, SubscriptionWrite};
+pub use connection::{
+    EventStoreDBConnection, OperationsClient, ProjectionManagementClient, UserManagementClient,
+};
+pub use gossip::VNodeState;
 pub use grpc_connection::{ConnectionSettings, ConnectionSettingsParseError};
 pub use types::*;
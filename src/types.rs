@@ -1,13 +1,15 @@
 //! Common types used across the library.
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
 use serde::de::{Deserialize, Visitor};
 use serde::ser::Serialize;
 use uuid::Uuid;
 
+use crate::gossip::VNodeState;
+
 use futures::Stream;
 use serde::{Deserializer, Serializer};
 use thiserror::Error;
@@ -15,12 +17,199 @@ use tonic::Status;
 
 /// Represents a reconnection strategy when a connection has dropped or is
 /// about to be created.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Retry {
     Undefinately,
     Only(usize),
 }
 
+impl Retry {
+    pub(crate) fn allows(self, attempts: usize) -> bool {
+        match self {
+            Retry::Undefinately => true,
+            Retry::Only(max) => attempts <= max,
+        }
+    }
+}
+
+/// Determines how many times and how often an operation is retried. By
+/// default, an operation is retried indefinitely, every 500ms.
+#[derive(Copy, Clone, Debug)]
+pub struct RetryPolicy {
+    pub(crate) limit: Retry,
+    pub(crate) delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the given retry limit.
+    pub fn new(limit: Retry) -> Self {
+        RetryPolicy {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Never retries.
+    pub fn none() -> Self {
+        RetryPolicy::new(Retry::Only(0))
+    }
+
+    /// Delay between two attempts. Default: 500ms.
+    pub fn delay(self, delay: Duration) -> Self {
+        RetryPolicy { delay, ..self }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            limit: Retry::Undefinately,
+            delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Determines how a connection retries to reach a node, both in single-node
+/// and cluster mode. The delay between two attempts doubles after each
+/// failure, up to `max_delay`. When attempts are exhausted, the requests
+/// waiting for a node fail with `Error::DiscoveryFailed`.
+///
+/// The limit counts retries after the first attempt, like in `RetryPolicy`:
+/// `Retry::Only(2)` gives up after 3 attempts. By default, the policy retries
+/// 2 times, starting with a 500ms delay capped at 10s, with jitter, which
+/// matches the `maxDiscoverAttempts` and `discoveryInterval` defaults.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReconnectionPolicy {
+    pub(crate) limit: Retry,
+    pub(crate) delay: Duration,
+    pub(crate) max_delay: Duration,
+    pub(crate) jitter: bool,
+}
+
+impl ReconnectionPolicy {
+    /// Creates a policy with the given limit of retries.
+    pub fn new(limit: Retry) -> Self {
+        ReconnectionPolicy {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Delay after the first failed attempt. Default: 500ms.
+    pub fn delay(self, delay: Duration) -> Self {
+        ReconnectionPolicy { delay, ..self }
+    }
+
+    /// Upper bound of the delay between two attempts. Default: 10s.
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        ReconnectionPolicy { max_delay, ..self }
+    }
+
+    /// Randomizes each delay between half and all of its value, so clients
+    /// don't reconnect all at once. Default: `true`.
+    pub fn jitter(self, jitter: bool) -> Self {
+        ReconnectionPolicy { jitter, ..self }
+    }
+
+    /// Tells if the connection should give up after the given number of
+    /// failed attempts.
+    pub(crate) fn exhausted(&self, attempts: usize) -> bool {
+        !self.limit.allows(attempts)
+    }
+
+    /// Delay to wait for after the given number of failed attempts.
+    pub(crate) fn backoff<R: rand::Rng>(&self, attempts: usize, rng: &mut R) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(31) as u32;
+        let delay = self
+            .delay
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if !self.jitter {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let spread = half.as_nanos().min(u64::MAX as u128) as u64;
+
+        half + Duration::from_nanos(rng.gen_range(0, spread + 1))
+    }
+}
+
+impl Default for ReconnectionPolicy {
+    fn default() -> Self {
+        ReconnectionPolicy {
+            limit: Retry::Only(2),
+            delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+#[test]
+fn test_deterministic_event_id() {
+    let namespace = Uuid::new_v4();
+    let event =
+        |key: &str| EventData::binary("foo", Bytes::new()).with_deterministic_id(namespace, key);
+
+    assert_eq!(event("key").id_opt, event("key").id_opt);
+    assert_ne!(event("key").id_opt, event("other-key").id_opt);
+    assert_ne!(
+        event("key").id_opt,
+        EventData::binary("foo", Bytes::new())
+            .with_deterministic_id(Uuid::new_v4(), "key")
+            .id_opt
+    );
+}
+
+#[test]
+fn test_retry_limit_boundary() {
+    let limit = Retry::Only(2);
+    let reconnection = ReconnectionPolicy::new(limit);
+    let retry = RetryPolicy::new(limit);
+
+    // The first attempt plus 2 retries.
+    for attempts in 0..=2 {
+        assert!(retry.limit.allows(attempts));
+        assert!(!reconnection.exhausted(attempts));
+    }
+
+    assert!(!retry.limit.allows(3));
+    assert!(reconnection.exhausted(3));
+
+    assert!(!RetryPolicy::none().limit.allows(1));
+    assert!(ReconnectionPolicy::new(Retry::Only(0)).exhausted(1));
+}
+
+#[test]
+fn test_reconnection_policy_backoff() {
+    let mut rng = rand::thread_rng();
+    let policy = ReconnectionPolicy::new(Retry::Only(10))
+        .delay(Duration::from_millis(100))
+        .max_delay(Duration::from_secs(1))
+        .jitter(false);
+
+    assert_eq!(policy.backoff(1, &mut rng), Duration::from_millis(100));
+    assert_eq!(policy.backoff(3, &mut rng), Duration::from_millis(400));
+    assert_eq!(policy.backoff(5, &mut rng), Duration::from_secs(1));
+    assert_eq!(policy.backoff(200, &mut rng), Duration::from_secs(1));
+    assert!(!policy.exhausted(10));
+    assert!(policy.exhausted(11));
+
+    let policy = policy.jitter(true);
+
+    for attempts in 1..10 {
+        let delay = policy.backoff(attempts, &mut rng);
+
+        assert!(delay <= Duration::from_secs(1));
+        assert!(delay >= policy.jitter(false).backoff(attempts, &mut rng) / 2);
+    }
+
+    assert!(!ReconnectionPolicy::new(Retry::Undefinately).exhausted(usize::MAX));
+}
+
 /// Holds login and password information.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Credentials {
@@ -190,6 +379,36 @@ pub struct WriteResult {
 
     /// `Position` of the write.
     pub position: Position,
+
+    /// `true` when the server recognized the events as already written,
+    /// based on their ids, and didn't append them again. The stream revision
+    /// is unchanged in that case.
+    pub deduplicated: bool,
+}
+
+/// Returned after a chunked append. Chunks are written in order and the
+/// append stops at the first failing chunk, the events of the previous
+/// chunks remaining written.
+#[derive(Debug)]
+pub struct ChunkedWriteResult {
+    /// Number of events written by the successful chunks.
+    pub events_written: usize,
+
+    /// Number of chunks successfully written.
+    pub chunks_written: usize,
+
+    /// Result of the last successful chunk. `None` if no chunk got written.
+    pub last_write: Option<WriteResult>,
+
+    /// Why the append stopped. `None` if every event got written.
+    pub error: Option<Error>,
+}
+
+impl ChunkedWriteResult {
+    /// Checks if every event got written.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
 }
 
 #[derive(Debug)]
@@ -245,6 +464,12 @@ pub struct RecordedEvent {
     /// Indicates wheter the content is internally marked as JSON.
     pub is_json: bool,
 
+    /// Content type of the payload, like `application/json`.
+    pub content_type: String,
+
+    /// When the event was written. `None` if the server didn't provide it.
+    pub created: Option<SystemTime>,
+
     /// An event position in the $all stream.
     pub position: Position,
 }
@@ -361,6 +586,13 @@ impl Payload {
             Payload::Binary(bytes) => bytes,
         }
     }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Payload::Json(bytes) => bytes.len(),
+            Payload::Binary(bytes) => bytes.len(),
+        }
+    }
 }
 
 /// Holds data of event about to be sent to the server.
@@ -412,6 +644,33 @@ impl EventData {
         }
     }
 
+    /// Approximate size of the event once written, used to bound the size of
+    /// chunked appends.
+    pub(crate) fn size(&self) -> usize {
+        self.event_type.len()
+            + self.payload.len()
+            + self.custom_metadata.as_ref().map_or(0, Payload::len)
+    }
+
+    /// Sets an id derived from the given namespace and key, using UUID v5.
+    /// The same namespace and key always give the same id, so retrying an
+    /// append of that event doesn't duplicate it on the server.
+    ///
+    /// ```
+    /// # use eventstore::EventData;
+    /// # use uuid::Uuid;
+    /// let namespace = Uuid::parse_str("6ba7b811-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    /// let event = EventData::json("order-placed", "order-42")
+    ///     .unwrap()
+    ///     .with_deterministic_id(namespace, "message-1234");
+    /// ```
+    pub fn with_deterministic_id<K>(self, namespace: Uuid, key: K) -> Self
+    where
+        K: AsRef<[u8]>,
+    {
+        self.id(Uuid::new_v5(&namespace, key.as_ref()))
+    }
+
     /// Assigns a JSON metadata to this event.
     pub fn metadata_as_json<P>(self, payload: P) -> EventData
     where
@@ -582,6 +841,173 @@ pub struct StreamAcl {
     pub meta_write_roles: Option<Vec<String>>,
 }
 
+impl StreamAcl {
+    fn is_empty(&self) -> bool {
+        self.read_roles.is_none()
+            && self.write_roles.is_none()
+            && self.delete_roles.is_none()
+            && self.meta_read_roles.is_none()
+            && self.meta_write_roles.is_none()
+    }
+}
+
+// The server accepts either a single role or a list of roles.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Roles {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl From<Roles> for Vec<String> {
+    fn from(roles: Roles) -> Self {
+        match roles {
+            Roles::Single(role) => vec![role],
+            Roles::Multiple(roles) => roles,
+        }
+    }
+}
+
+// Wire representation of a `StreamAcl`, using the names the server expects.
+#[derive(Serialize, Deserialize, Default)]
+struct StreamAclRepr {
+    #[serde(rename = "$r", default, skip_serializing_if = "Option::is_none")]
+    read_roles: Option<Roles>,
+    #[serde(rename = "$w", default, skip_serializing_if = "Option::is_none")]
+    write_roles: Option<Roles>,
+    #[serde(rename = "$d", default, skip_serializing_if = "Option::is_none")]
+    delete_roles: Option<Roles>,
+    #[serde(rename = "$mr", default, skip_serializing_if = "Option::is_none")]
+    meta_read_roles: Option<Roles>,
+    #[serde(rename = "$mw", default, skip_serializing_if = "Option::is_none")]
+    meta_write_roles: Option<Roles>,
+}
+
+// Wire representation of a `StreamMetadata`, using the names the server
+// expects. Durations are expressed in seconds.
+#[derive(Serialize, Deserialize, Default)]
+struct StreamMetadataRepr {
+    #[serde(rename = "$maxCount", default, skip_serializing_if = "Option::is_none")]
+    max_count: Option<u64>,
+    #[serde(rename = "$maxAge", default, skip_serializing_if = "Option::is_none")]
+    max_age: Option<u64>,
+    #[serde(rename = "$tb", default, skip_serializing_if = "Option::is_none")]
+    truncate_before: Option<u64>,
+    #[serde(
+        rename = "$cacheControl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    cache_control: Option<u64>,
+    #[serde(rename = "$acl", default, skip_serializing_if = "Option::is_none")]
+    acl: Option<StreamAclRepr>,
+    #[serde(flatten)]
+    custom_properties: HashMap<String, serde_json::Value>,
+}
+
+impl Serialize for StreamMetadata {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let acl = if self.acl.is_empty() {
+            None
+        } else {
+            let acl = self.acl.clone();
+
+            Some(StreamAclRepr {
+                read_roles: acl.read_roles.map(Roles::Multiple),
+                write_roles: acl.write_roles.map(Roles::Multiple),
+                delete_roles: acl.delete_roles.map(Roles::Multiple),
+                meta_read_roles: acl.meta_read_roles.map(Roles::Multiple),
+                meta_write_roles: acl.meta_write_roles.map(Roles::Multiple),
+            })
+        };
+
+        let repr = StreamMetadataRepr {
+            max_count: self.max_count,
+            max_age: self.max_age.map(|d| d.as_secs()),
+            truncate_before: self.truncate_before,
+            cache_control: self.cache_control.map(|d| d.as_secs()),
+            acl,
+            custom_properties: self.custom_properties.clone(),
+        };
+
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StreamMetadata {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = StreamMetadataRepr::deserialize(deserializer)?;
+        let acl = repr.acl.unwrap_or_default();
+
+        Ok(StreamMetadata {
+            max_count: repr.max_count,
+            max_age: repr.max_age.map(Duration::from_secs),
+            truncate_before: repr.truncate_before,
+            cache_control: repr.cache_control.map(Duration::from_secs),
+            acl: StreamAcl {
+                read_roles: acl.read_roles.map(Vec::from),
+                write_roles: acl.write_roles.map(Vec::from),
+                delete_roles: acl.delete_roles.map(Vec::from),
+                meta_read_roles: acl.meta_read_roles.map(Vec::from),
+                meta_write_roles: acl.meta_write_roles.map(Vec::from),
+            },
+            custom_properties: repr.custom_properties,
+        })
+    }
+}
+
+#[test]
+fn test_stream_metadata_wire_format() {
+    let metadata = StreamMetadata::builder()
+        .max_count(10)
+        .max_age(Duration::from_secs(3_600))
+        .truncate_before(2)
+        .acl(StreamAcl {
+            read_roles: Some(vec!["$all".to_string()]),
+            ..Default::default()
+        })
+        .insert_custom_property("tenant".to_string(), "acme")
+        .build();
+
+    let json = serde_json::to_value(&metadata).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "$maxCount": 10,
+            "$maxAge": 3600,
+            "$tb": 2,
+            "$acl": { "$r": ["$all"] },
+            "tenant": "acme",
+        })
+    );
+
+    let metadata: StreamMetadata = serde_json::from_value(serde_json::json!({
+        "$cacheControl": 10,
+        "$acl": { "$w": "$admins", "$mr": ["ops", "$admins"] },
+        "tenant": "acme",
+    }))
+    .unwrap();
+
+    assert_eq!(metadata.cache_control, Some(Duration::from_secs(10)));
+    assert_eq!(metadata.acl.write_roles, Some(vec!["$admins".to_string()]));
+    assert_eq!(
+        metadata.acl.meta_read_roles,
+        Some(vec!["ops".to_string(), "$admins".to_string()])
+    );
+    assert_eq!(metadata.acl.read_roles, None);
+    assert_eq!(
+        metadata.custom_properties.get("tenant"),
+        Some(&serde_json::json!("acme"))
+    );
+}
+
 /// Read part of a persistent subscription, isomorphic to a stream of events.
 pub struct PersistentSubRead {
     pub(crate) inner: Box<dyn Stream<Item = PersistentSubEvent> + Send + Unpin>,
@@ -600,28 +1026,60 @@ impl PersistentSubRead {
 }
 
 /// Events related to a subscription.
+#[derive(Debug)]
 pub enum SubEvent {
-    /// Indicates the subscription has been confirmed by the server.
-    Confirmed,
+    /// Indicates the subscription has been confirmed by the server. Holds
+    /// the subscription id. A subscription is confirmed again each time it
+    /// resubscribes.
+    Confirmed(String),
 
     /// An event notification from the server.
-    EventAppeared {
-        event: Box<ResolvedEvent>,
-        retry_count: usize,
-    },
+    EventAppeared(Box<ResolvedEvent>),
+
+    /// Position in the transaction file the server has reached so far. Sent
+    /// periodically by the server on `$all` subscriptions, even when no
+    /// event matches the subscription filter.
+    Checkpoint(Position),
+
+    /// Indicates the subscription has dropped. It's always the last item of
+    /// a subscription.
+    Dropped(SubscriptionDropReason),
+}
 
-    /// Indicates the subscription has dropped.
-    Dropped,
+/// Items emitted when reading `$all` with checkpoints.
+#[derive(Debug)]
+pub enum ReadAllItem {
+    /// An event read from `$all`.
+    Event(Box<ResolvedEvent>),
+
+    /// Position in the transaction file the server has reached so far.
+    Checkpoint(Position),
+}
+
+/// Why a subscription has dropped.
+#[derive(Debug)]
+pub enum SubscriptionDropReason {
+    /// The server ended the subscription.
+    ServerClosed,
+
+    /// The connection was closed, see `EventStoreDBConnection::close`.
+    ConnectionClosed,
+
+    /// The subscription failed with an error it couldn't recover from.
+    Error(Error),
 }
 
+/// Event delivered by a persistent subscription.
 #[derive(Debug)]
 pub struct PersistentSubEvent {
     pub inner: ResolvedEvent,
+    /// How many times the server already tried to deliver that event. It's
+    /// 0 on the first delivery.
     pub retry_count: usize,
 }
 
 /// Gathers every possible Nak actions.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NakAction {
     /// Client unknown on action. Let server decide.
     Unknown,
@@ -909,40 +1367,149 @@ pub enum Error {
     ConnectionClosed,
     #[error("Unmapped gRPC error: {0}.")]
     Grpc(Status),
+    #[error("{0}.")]
+    WrongExpectedVersion(WrongExpectedVersion),
+    #[error("Failed to discover a node after {0} attempts.")]
+    DiscoveryFailed(usize),
+    #[error("Failed to deserialize the server response: {0}.")]
+    DeserializationError(serde_json::Error),
+    #[error("Access denied.")]
+    AccessDenied,
+    #[error("The stream is deleted.")]
+    StreamDeleted,
+    #[error("Authentication is required.")]
+    NotAuthenticated,
+    #[error("The operation didn't complete before its deadline.")]
+    DeadlineExceeded,
+    #[error("The append exceeds the maximum size allowed by the server.")]
+    MaximumAppendSizeExceeded,
+    #[error("The persistent subscription doesn't exist.")]
+    PersistentSubscriptionNotFound,
+    #[error("The persistent subscription already exists.")]
+    PersistentSubscriptionExists,
+    #[error("The server didn't confirm the persistent subscription.")]
+    PersistentSubscriptionNotConfirmed,
+    #[error("The requested resource doesn't exist.")]
+    ResourceNotFound,
+    #[error("Failed to connect to {}:{}: {}.", .0.host, .0.port, .1)]
+    ConnectionFailed(Endpoint, Box<dyn std::error::Error + Send + Sync>),
+    #[error("Unexpected server response: {0}.")]
+    UnexpectedResponse(String),
 }
 
 impl Error {
     pub fn from_grpc(status: Status) -> Self {
-        match status.code() {
-            tonic::Code::Unavailable => Error::ServerError,
-            _ => {
-                let metadata = status.metadata();
-                if let Some(tpe) = metadata.get("exception").and_then(|e| e.to_str().ok()) {
-                    if let "not-leader" = tpe {
-                        let endpoint = metadata
-                            .get("leader-endpoint-host")
-                            .zip(metadata.get("leader-endpoint-port"))
-                            .and_then(|(host, port)| {
-                                let host = host.to_str().ok()?;
-                                let port = port.to_str().ok()?;
-                                let host = host.to_string();
-                                let port = port.parse().ok()?;
-
-                                Some(Endpoint { host, port })
-                            });
-
-                        if let Some(leader) = endpoint {
-                            return Error::NotLeaderException(leader);
-                        }
+        let metadata = status.metadata();
+
+        if let Some(tpe) = metadata.get("exception").and_then(|e| e.to_str().ok()) {
+            match tpe {
+                "not-leader" => {
+                    let endpoint = metadata
+                        .get("leader-endpoint-host")
+                        .zip(metadata.get("leader-endpoint-port"))
+                        .and_then(|(host, port)| {
+                            let host = host.to_str().ok()?;
+                            let port = port.to_str().ok()?;
+                            let host = host.to_string();
+                            let port = port.parse().ok()?;
+
+                            Some(Endpoint { host, port })
+                        });
+
+                    if let Some(leader) = endpoint {
+                        return Error::NotLeaderException(leader);
                     }
                 }
 
-                Error::Grpc(status)
+                "access-denied" => return Error::AccessDenied,
+                "stream-deleted" => return Error::StreamDeleted,
+                "maximum-append-size-exceeded" => return Error::MaximumAppendSizeExceeded,
+                "persistent-subscription-does-not-exist" => {
+                    return Error::PersistentSubscriptionNotFound
+                }
+                "persistent-subscription-exists" => return Error::PersistentSubscriptionExists,
+                "user-not-found" | "scavenge-not-found" => return Error::ResourceNotFound,
+                _ => {}
             }
         }
+
+        match status.code() {
+            tonic::Code::Unavailable => Error::ServerError,
+            tonic::Code::PermissionDenied => Error::AccessDenied,
+            tonic::Code::Unauthenticated => Error::NotAuthenticated,
+            tonic::Code::DeadlineExceeded => Error::DeadlineExceeded,
+            tonic::Code::NotFound => Error::ResourceNotFound,
+            _ => Error::Grpc(status),
+        }
+    }
+
+    /// Indicates if the error is likely to go away by retrying the same
+    /// operation later, possibly on another node.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Error::ServerError
+                | Error::NotLeaderException(_)
+                | Error::DeadlineExceeded
+                | Error::DiscoveryFailed(_)
+                | Error::ConnectionFailed(_, _)
+        )
     }
 }
 
+#[test]
+fn test_error_from_grpc_exception() {
+    fn status(code: tonic::Code, exception: &'static str) -> Status {
+        let mut metadata = tonic::metadata::MetadataMap::new();
+
+        metadata.insert("exception", exception.parse().unwrap());
+
+        Status::with_metadata(code, "", metadata)
+    }
+
+    assert!(matches!(
+        Error::from_grpc(status(tonic::Code::Unknown, "stream-deleted")),
+        Error::StreamDeleted
+    ));
+    assert!(matches!(
+        Error::from_grpc(status(
+            tonic::Code::NotFound,
+            "persistent-subscription-does-not-exist"
+        )),
+        Error::PersistentSubscriptionNotFound
+    ));
+    assert!(matches!(
+        Error::from_grpc(status(tonic::Code::Unavailable, "access-denied")),
+        Error::AccessDenied
+    ));
+    assert!(matches!(
+        Error::from_grpc(Status::deadline_exceeded("")),
+        Error::DeadlineExceeded
+    ));
+    assert!(matches!(
+        Error::from_grpc(Status::unauthenticated("")),
+        Error::NotAuthenticated
+    ));
+    assert!(matches!(
+        Error::from_grpc(status(tonic::Code::Unknown, "something-else")),
+        Error::Grpc(_)
+    ));
+    assert!(Error::ServerError.is_transient());
+    assert!(!Error::AccessDenied.is_transient());
+
+    let endpoint = Endpoint {
+        host: "localhost".to_string(),
+        port: 2113,
+    };
+    let failed = Error::ConnectionFailed(endpoint, "connection refused".into());
+
+    assert!(failed.is_transient());
+    assert_eq!(
+        failed.to_string(),
+        "Failed to connect to localhost:2113: connection refused."
+    );
+}
+
 pub type Result<A> = std::result::Result<A, Error>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -999,3 +1566,236 @@ impl<A> ReadResult<A> {
         }
     }
 }
+
+/// Selects which projections are listed when requesting projection
+/// statistics.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProjectionListMode {
+    /// Lists all the projections, regardless of their mode.
+    All,
+
+    /// Lists only transient projections.
+    Transient,
+
+    /// Lists only continuous projections.
+    Continuous,
+
+    /// Lists only one-time projections.
+    OneTime,
+
+    /// Only targets the projection with the given name.
+    Name(String),
+}
+
+/// Running mode of a projection.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProjectionMode {
+    OneTime,
+    Transient,
+    Continuous,
+
+    /// A mode this client doesn't know about.
+    Unknown(String),
+}
+
+impl ProjectionMode {
+    pub(crate) fn parse(mode: &str) -> Self {
+        match mode {
+            "OneTime" => ProjectionMode::OneTime,
+            "Transient" => ProjectionMode::Transient,
+            "Continuous" => ProjectionMode::Continuous,
+            _ => ProjectionMode::Unknown(mode.to_string()),
+        }
+    }
+}
+
+/// Status of a projection as reported by the server.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProjectionStatus {
+    Creating,
+    Loading,
+    Loaded,
+    Preparing,
+    Prepared,
+    Starting,
+    LoadingStopped,
+    Running,
+    Stopping,
+    Aborting,
+    Stopped,
+    Completed,
+    Aborted,
+    Faulted,
+    Deleting,
+
+    /// A status this client doesn't know about.
+    Unknown(String),
+}
+
+impl ProjectionStatus {
+    /// The server could report statuses like `Stopped/Faulted`. A faulted
+    /// projection is reported as `Faulted`, whatever its other segments are.
+    /// Otherwise, only the first segment is considered.
+    pub(crate) fn parse(status: &str) -> Self {
+        let mut segments = status.split('/').map(str::trim);
+
+        if segments.clone().any(|segment| segment == "Faulted") {
+            return ProjectionStatus::Faulted;
+        }
+
+        let status = segments.next().unwrap_or_default();
+
+        match status {
+            "Creating" => ProjectionStatus::Creating,
+            "Loading" => ProjectionStatus::Loading,
+            "Loaded" => ProjectionStatus::Loaded,
+            "Preparing" => ProjectionStatus::Preparing,
+            "Prepared" => ProjectionStatus::Prepared,
+            "Starting" => ProjectionStatus::Starting,
+            "LoadingStopped" => ProjectionStatus::LoadingStopped,
+            "Running" => ProjectionStatus::Running,
+            "Stopping" => ProjectionStatus::Stopping,
+            "Aborting" => ProjectionStatus::Aborting,
+            "Stopped" => ProjectionStatus::Stopped,
+            "Completed" => ProjectionStatus::Completed,
+            "Aborted" => ProjectionStatus::Aborted,
+            "Faulted" => ProjectionStatus::Faulted,
+            "Deleting" => ProjectionStatus::Deleting,
+            _ => ProjectionStatus::Unknown(status.to_string()),
+        }
+    }
+}
+
+/// Where a projection is at when reading its source.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ProjectionPosition {
+    /// Position in the transaction file, for projections reading from `$all`.
+    All(Position),
+
+    /// Any other checkpoint tag (per-stream revisions for example), as
+    /// reported by the server.
+    Other(String),
+}
+
+impl ProjectionPosition {
+    /// Parses positions like `C:1234/P:1234`. Returns `None` if the server
+    /// didn't report any position.
+    pub(crate) fn parse(position: &str) -> Option<Self> {
+        let position = position.trim();
+
+        if position.is_empty() {
+            return None;
+        }
+
+        let parsed = position.split_once('/').and_then(|(commit, prepare)| {
+            let commit = commit.trim().strip_prefix("C:")?.parse().ok()?;
+            let prepare = prepare.trim().strip_prefix("P:")?.parse().ok()?;
+
+            Some(Position { commit, prepare })
+        });
+
+        match parsed {
+            Some(pos) => Some(ProjectionPosition::All(pos)),
+            None => Some(ProjectionPosition::Other(position.to_string())),
+        }
+    }
+}
+
+/// Statistics of a projection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectionDetails {
+    pub name: String,
+    pub effective_name: String,
+    pub mode: ProjectionMode,
+    pub status: ProjectionStatus,
+
+    /// Why the projection is in its current status, if faulted for example.
+    pub state_reason: String,
+    pub version: i64,
+    pub epoch: i64,
+    pub core_processing_time: i64,
+    pub writes_in_progress: i32,
+    pub reads_in_progress: i32,
+    pub partitions_cached: i32,
+
+    /// Current position of the projection.
+    pub position: Option<ProjectionPosition>,
+
+    /// Progress percentage of the projection. `None` if the server doesn't
+    /// know it.
+    pub progress: Option<f32>,
+
+    /// Position of the last checkpoint written by the projection.
+    pub last_checkpoint: Option<ProjectionPosition>,
+    pub events_processed_after_restart: i64,
+    pub checkpoint_status: String,
+    pub buffered_events: i64,
+    pub write_pending_events_before_checkpoint: i32,
+    pub write_pending_events_after_checkpoint: i32,
+}
+
+/// Information about a user account.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UserDetails {
+    pub login: String,
+    pub full_name: String,
+    pub groups: Vec<String>,
+    pub disabled: bool,
+
+    /// When the account was last updated. `None` if the server doesn't know.
+    pub last_updated: Option<SystemTime>,
+}
+
+/// Returned when starting or stopping a scavenge.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ScavengeResult {
+    /// Scavenge id, to be used to stop the scavenge.
+    pub id: String,
+    pub status: ScavengeStatus,
+}
+
+/// State of a scavenge.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ScavengeStatus {
+    Started,
+    InProgress,
+    Stopped,
+}
+
+/// Changes of the connection state, as returned by
+/// `EventStoreDBConnection::state_changes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The connection is trying to reach the given node.
+    Connecting(Endpoint),
+
+    /// The connection reached the given node. The node state is only known
+    /// in cluster mode.
+    Connected(Endpoint, Option<VNodeState>),
+
+    /// The connection lost the node it was using.
+    Disconnected(DisconnectReason),
+
+    /// The connection moved to a different node than the one it was using.
+    NodeReselected {
+        previous: Endpoint,
+        current: Endpoint,
+    },
+
+    /// No node could be reached after the given number of attempts. Pending
+    /// requests failed with `Error::DiscoveryFailed`.
+    DiscoveryFailed(usize),
+}
+
+/// Why the connection stopped using a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The node became unreachable.
+    NodeUnavailable,
+
+    /// The node isn't the leader anymore. Holds the new leader endpoint.
+    NotLeader(Endpoint),
+
+    /// The connection was closed with `EventStoreDBConnection::close`.
+    Closed,
+}